@@ -0,0 +1,514 @@
+//! `disco` command-line tool for organizers and door staff.
+
+mod output;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use disco_client::{accounts, instructions, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
+
+use crate::output::{OutputFormat, Printer};
+
+#[derive(Parser)]
+#[clap(
+    name = "disco",
+    version,
+    about = "Manage disco events from the command line"
+)]
+struct Cli {
+    /// Keypair used to sign and pay for transactions
+    #[clap(
+        long,
+        short,
+        global = true,
+        env = "DISCO_KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+    /// Cluster RPC URL
+    #[clap(
+        long,
+        short,
+        global = true,
+        env = "DISCO_URL",
+        default_value = "http://localhost:8899"
+    )]
+    url: String,
+    /// Output format
+    #[clap(long, short, global = true, arg_enum, default_value = "table")]
+    output: OutputFormat,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create and inspect events
+    #[clap(subcommand)]
+    Event(EventCommand),
+    /// Create ticket tiers
+    #[clap(subcommand)]
+    Tier(TierCommand),
+    /// Manage the door staff of an event
+    #[clap(subcommand)]
+    Collaborator(CollaboratorCommand),
+    /// Buy tickets
+    #[clap(subcommand)]
+    Tickets(TicketsCommand),
+    /// Check an attendee in, signing as a collaborator
+    CheckIn(CheckInArgs),
+    /// Withdraw funds from the event vault
+    Withdraw(WithdrawArgs),
+}
+
+#[derive(Subcommand)]
+enum EventCommand {
+    /// Create an event owned by the signer
+    Create {
+        #[clap(long)]
+        title: String,
+        #[clap(long)]
+        accepted_mint: Pubkey,
+    },
+    /// Set when an event happens
+    Schedule {
+        #[clap(long)]
+        event_base: Pubkey,
+        /// Unix timestamp, 0 to unschedule
+        #[clap(long)]
+        start_time: i64,
+        /// Unix timestamp, 0 to unschedule
+        #[clap(long)]
+        end_time: i64,
+        /// IANA timezone name, e.g. Europe/Brussels
+        #[clap(long, default_value = "")]
+        timezone: String,
+    },
+    /// Set where an event happens and how it is described
+    Describe {
+        #[clap(long)]
+        event_base: Pubkey,
+        /// Street address or geohash
+        #[clap(long, default_value = "")]
+        location: String,
+        #[clap(long = "tag")]
+        tags: Vec<String>,
+        /// URI of a JSON document following the event metadata schema
+        #[clap(long, default_value = "")]
+        metadata_uri: String,
+    },
+    /// Print an event and, optionally, some of its tiers
+    Show {
+        #[clap(long)]
+        event_base: Pubkey,
+        /// Ticket tiers to include, by event ticket base
+        #[clap(long = "tier")]
+        tiers: Vec<Pubkey>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TierCommand {
+    /// Create a ticket tier with its mint and metadata
+    Create {
+        #[clap(long)]
+        event_base: Pubkey,
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        symbol: String,
+        #[clap(long)]
+        uri: String,
+        #[clap(long)]
+        price: u32,
+        #[clap(long)]
+        quantity: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum CollaboratorCommand {
+    /// Allow a wallet to check attendees in
+    Add {
+        #[clap(long)]
+        event_base: Pubkey,
+        /// Wallet of the collaborator
+        #[clap(long)]
+        collaborator: Pubkey,
+    },
+    /// Revoke a collaborator
+    Remove {
+        #[clap(long)]
+        event_base: Pubkey,
+        /// Wallet of the collaborator
+        #[clap(long)]
+        collaborator: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+enum TicketsCommand {
+    /// Buy tickets from a tier, paying with the signer's associated account
+    Buy {
+        #[clap(long)]
+        event_base: Pubkey,
+        #[clap(long)]
+        event_ticket_base: Pubkey,
+        #[clap(long)]
+        quantity: u32,
+        /// Wallet receiving the tickets, defaults to the signer
+        #[clap(long)]
+        recipient: Option<Pubkey>,
+    },
+}
+
+#[derive(Args)]
+struct CheckInArgs {
+    #[clap(long)]
+    event_base: Pubkey,
+    #[clap(long)]
+    event_ticket_base: Pubkey,
+    /// Keypair of the attendee, who has to co-sign the check-in
+    #[clap(long)]
+    attendee_keypair: String,
+    #[clap(long, default_value = "1")]
+    quantity: u32,
+}
+
+#[derive(Args)]
+struct WithdrawArgs {
+    #[clap(long)]
+    event_base: Pubkey,
+    #[clap(long)]
+    amount: u64,
+    /// Token account receiving the funds, defaults to the signer's associated account
+    #[clap(long)]
+    destination: Option<Pubkey>,
+}
+
+struct Client {
+    rpc: RpcClient,
+    payer: Keypair,
+    printer: Printer,
+}
+
+impl Client {
+    fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    fn fetch<T: anchor_lang::AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let account = self
+            .rpc
+            .get_account(address)
+            .with_context(|| format!("account {} not found", address))?;
+
+        accounts::deserialize_owned(&account.owner, &account.data)
+            .map_err(|err| anyhow!("failed to deserialize {}: {}", address, err))
+    }
+
+    /// Returns the associated token account of `owner`, creating it first if needed.
+    fn associated_token_account(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        instructions: &mut Vec<Instruction>,
+    ) -> Pubkey {
+        let address = get_associated_token_address(owner, mint);
+
+        if self.rpc.get_account(&address).is_err() {
+            instructions.push(create_associated_token_account(
+                &self.payer.pubkey(),
+                owner,
+                mint,
+            ));
+        }
+
+        address
+    }
+}
+
+fn read_keypair(path: &str) -> Result<Keypair> {
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => format!("{}/{}", std::env::var("HOME")?, rest),
+        None => path.to_string(),
+    };
+
+    read_keypair_file(&path).map_err(|err| anyhow!("failed to read keypair {}: {}", path, err))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = Client {
+        rpc: RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed()),
+        payer: read_keypair(&cli.keypair)?,
+        printer: Printer::new(cli.output),
+    };
+
+    match cli.command {
+        Command::Event(EventCommand::Create {
+            title,
+            accepted_mint,
+        }) => {
+            let event_base = Keypair::new().pubkey();
+            let signature = client.send(
+                &[instructions::create_event(
+                    client.payer.pubkey(),
+                    event_base,
+                    accepted_mint,
+                    title,
+                )],
+                &[],
+            )?;
+
+            client.printer.print(&[
+                ("signature", signature.to_string()),
+                ("event_base", event_base.to_string()),
+                ("event", pda::find_event_address(&event_base).0.to_string()),
+            ]);
+        }
+        Command::Event(EventCommand::Schedule {
+            event_base,
+            start_time,
+            end_time,
+            timezone,
+        }) => {
+            let signature = client.send(
+                &[instructions::update_event_schedule(
+                    client.payer.pubkey(),
+                    event_base,
+                    start_time,
+                    end_time,
+                    timezone,
+                )],
+                &[],
+            )?;
+
+            client
+                .printer
+                .print(&[("signature", signature.to_string())]);
+        }
+        Command::Event(EventCommand::Describe {
+            event_base,
+            location,
+            tags,
+            metadata_uri,
+        }) => {
+            let signature = client.send(
+                &[instructions::update_event_details(
+                    client.payer.pubkey(),
+                    event_base,
+                    location,
+                    tags,
+                    metadata_uri,
+                )],
+                &[],
+            )?;
+
+            client
+                .printer
+                .print(&[("signature", signature.to_string())]);
+        }
+        Command::Event(EventCommand::Show { event_base, tiers }) => {
+            let event_address = pda::find_event_address(&event_base).0;
+            let event: accounts::Event = client.fetch(&event_address)?;
+            let event_vault = client
+                .rpc
+                .get_token_account_balance(&pda::find_event_vault_address(&event_address).0)?;
+
+            client
+                .printer
+                .print_event(&event_address, &event, &event_vault.amount);
+
+            for event_ticket_base in tiers {
+                let event_ticket_address =
+                    pda::find_event_ticket_address(&event_address, &event_ticket_base).0;
+                let event_ticket: accounts::EventTicket = client.fetch(&event_ticket_address)?;
+
+                client
+                    .printer
+                    .print_event_ticket(&event_ticket_address, &event_ticket);
+            }
+        }
+        Command::Tier(TierCommand::Create {
+            event_base,
+            name,
+            symbol,
+            uri,
+            price,
+            quantity,
+        }) => {
+            let event_ticket_base = Keypair::new().pubkey();
+            let signature = client.send(
+                &[instructions::create_event_ticket(
+                    client.payer.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    name,
+                    symbol,
+                    uri,
+                    price,
+                    quantity,
+                )],
+                &[],
+            )?;
+            let event = pda::find_event_address(&event_base).0;
+
+            client.printer.print(&[
+                ("signature", signature.to_string()),
+                ("event_ticket_base", event_ticket_base.to_string()),
+                (
+                    "event_ticket",
+                    pda::find_event_ticket_address(&event, &event_ticket_base)
+                        .0
+                        .to_string(),
+                ),
+            ]);
+        }
+        Command::Collaborator(CollaboratorCommand::Add {
+            event_base,
+            collaborator,
+        }) => {
+            let signature = client.send(
+                &[instructions::create_collaborator(
+                    client.payer.pubkey(),
+                    event_base,
+                    collaborator,
+                )],
+                &[],
+            )?;
+
+            client
+                .printer
+                .print(&[("signature", signature.to_string())]);
+        }
+        Command::Collaborator(CollaboratorCommand::Remove {
+            event_base,
+            collaborator,
+        }) => {
+            let signature = client.send(
+                &[instructions::delete_collaborator(
+                    client.payer.pubkey(),
+                    event_base,
+                    collaborator,
+                )],
+                &[],
+            )?;
+
+            client
+                .printer
+                .print(&[("signature", signature.to_string())]);
+        }
+        Command::Tickets(TicketsCommand::Buy {
+            event_base,
+            event_ticket_base,
+            quantity,
+            recipient,
+        }) => {
+            let buyer = client.payer.pubkey();
+            let recipient = recipient.unwrap_or(buyer);
+            let event_address = pda::find_event_address(&event_base).0;
+            let event_ticket_address =
+                pda::find_event_ticket_address(&event_address, &event_ticket_base).0;
+            let ticket_mint =
+                pda::find_ticket_mint_address(&event_address, &event_ticket_address).0;
+            let event: accounts::Event = client.fetch(&event_address)?;
+            let config: accounts::Config = client.fetch(&pda::find_config_address().0)?;
+            let signature = client.send(
+                &[instructions::buy_tickets(
+                    buyer,
+                    buyer,
+                    recipient,
+                    event_base,
+                    event_ticket_base,
+                    get_associated_token_address(&buyer, &event.accepted_mint),
+                    get_associated_token_address(&config.fee_treasury, &event.accepted_mint),
+                    quantity,
+                )],
+                &[],
+            )?;
+            let ticket_vault = get_associated_token_address(&recipient, &ticket_mint);
+
+            client.printer.print(&[
+                ("signature", signature.to_string()),
+                ("ticket_vault", ticket_vault.to_string()),
+            ]);
+        }
+        Command::CheckIn(CheckInArgs {
+            event_base,
+            event_ticket_base,
+            attendee_keypair,
+            quantity,
+        }) => {
+            let attendee = read_keypair(&attendee_keypair)?;
+            let event_address = pda::find_event_address(&event_base).0;
+            let event_ticket_address =
+                pda::find_event_ticket_address(&event_address, &event_ticket_base).0;
+            let ticket_mint =
+                pda::find_ticket_mint_address(&event_address, &event_ticket_address).0;
+            let signature = client.send(
+                &[instructions::check_in(
+                    client.payer.pubkey(),
+                    client.payer.pubkey(),
+                    attendee.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    get_associated_token_address(&attendee.pubkey(), &ticket_mint),
+                    quantity,
+                )],
+                &[&attendee],
+            )?;
+
+            client
+                .printer
+                .print(&[("signature", signature.to_string())]);
+        }
+        Command::Withdraw(WithdrawArgs {
+            event_base,
+            amount,
+            destination,
+        }) => {
+            let authority = client.payer.pubkey();
+            let event: accounts::Event = client.fetch(&pda::find_event_address(&event_base).0)?;
+            let mut ixs = vec![];
+            let destination_vault = match destination {
+                Some(destination) => destination,
+                None => client.associated_token_account(&authority, &event.accepted_mint, &mut ixs),
+            };
+
+            ixs.push(instructions::withdraw(
+                authority,
+                event_base,
+                destination_vault,
+                amount,
+            ));
+
+            let signature = client.send(&ixs, &[])?;
+
+            client.printer.print(&[
+                ("signature", signature.to_string()),
+                ("destination", destination_vault.to_string()),
+            ]);
+        }
+    }
+
+    Ok(())
+}
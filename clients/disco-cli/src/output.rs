@@ -0,0 +1,86 @@
+use clap::ArgEnum;
+use disco_client::accounts::{Event, EventTicket};
+use serde_json::{json, Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(ArgEnum, Clone, Copy)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+pub struct Printer {
+    format: OutputFormat,
+}
+
+impl Printer {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn print(&self, rows: &[(&str, String)]) {
+        match self.format {
+            OutputFormat::Table => {
+                let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+
+                for (key, value) in rows {
+                    println!("{:width$}  {}", key, value, width = width);
+                }
+            }
+            OutputFormat::Json => {
+                let object: Map<String, Value> = rows
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), json!(value)))
+                    .collect();
+
+                println!("{}", Value::Object(object));
+            }
+        }
+    }
+
+    pub fn print_event(&self, address: &Pubkey, event: &Event, vault_balance: &str) {
+        self.print(&[
+            ("event", address.to_string()),
+            ("title", event.event_title.clone()),
+            ("authority", event.authority.to_string()),
+            ("accepted_mint", event.accepted_mint.to_string()),
+            ("vault_balance", vault_balance.to_string()),
+            ("organizer", optional(event.organizer)),
+            ("series", optional(event.series)),
+            ("venue", optional(event.venue)),
+            ("total_capacity", event.total_capacity.to_string()),
+            ("total_sold", event.total_sold.to_string()),
+            ("paused", event.paused.to_string()),
+            ("start_time", event.start_time.to_string()),
+            ("end_time", event.end_time.to_string()),
+            ("timezone", event.timezone.clone()),
+            ("location", event.location.clone()),
+            ("tags", event.tags.join(",")),
+            ("metadata_uri", event.metadata_uri.clone()),
+            ("relayer", optional(event.relayer)),
+            ("relayer_fee", event.relayer_fee.to_string()),
+            ("relayer_owed", event.relayer_owed.to_string()),
+            ("rebates_owed", event.rebates_owed.to_string()),
+        ]);
+    }
+
+    pub fn print_event_ticket(&self, address: &Pubkey, event_ticket: &EventTicket) {
+        if let OutputFormat::Table = self.format {
+            println!();
+        }
+
+        self.print(&[
+            ("event_ticket", address.to_string()),
+            ("price", event_ticket.price.to_string()),
+            ("quantity", event_ticket.quantity.to_string()),
+            ("sold", event_ticket.sold.to_string()),
+            ("used", event_ticket.used.to_string()),
+            ("comps", event_ticket.comps.to_string()),
+            ("available", event_ticket.available().to_string()),
+        ]);
+    }
+}
+
+fn optional(value: Option<Pubkey>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
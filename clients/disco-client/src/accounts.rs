@@ -0,0 +1,23 @@
+//! Deserializers for the accounts owned by the `disco` program.
+
+use anchor_lang::{prelude::*, AccountDeserialize};
+
+pub use disco::{
+    Affiliate, AuctionReceipt, Collaborator, Config, Entry, Event, EventTicket, Organizer,
+    PassUsage, Promo, Raffle, RaffleEntry, SeatMap, SeatReservation, Series, TicketHold, Venue,
+    Waitlist, WaitlistEntry, WalletPurchase,
+};
+
+/// Deserializes raw account data, checking the Anchor discriminator first.
+pub fn deserialize<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+/// Deserializes an account fetched from RPC, making sure it is owned by the program.
+pub fn deserialize_owned<T: AccountDeserialize>(owner: &Pubkey, data: &[u8]) -> Result<T> {
+    if owner != &crate::ID {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    deserialize(data)
+}
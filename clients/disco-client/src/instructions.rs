@@ -0,0 +1,2002 @@
+//! Typed builders for every `disco` instruction. Accounts that are PDAs are
+//! derived from their bases, everything else has to be provided by the caller.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        system_program, sysvar,
+    },
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token,
+};
+use disco::{accounts, instruction, DiscountKind, PricingCurve};
+
+use crate::{pda::*, ID};
+
+pub fn create_event(
+    authority: Pubkey,
+    event_base: Pubkey,
+    accepted_mint: Pubkey,
+    event_title: String,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateEvent {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            accepted_mint,
+            event_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateEvent { event_title }.data(),
+    }
+}
+
+pub fn initialize_config(admin: Pubkey, fee_bps: u16, fee_treasury: Pubkey) -> Instruction {
+    let program_data = find_program_data_address().0;
+    let config = find_config_address().0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::InitializeConfig {
+            system_program: system_program::ID,
+            admin,
+            program_data,
+            config,
+        }
+        .to_account_metas(None),
+        data: instruction::InitializeConfig {
+            fee_bps,
+            fee_treasury,
+        }
+        .data(),
+    }
+}
+
+pub fn set_config_admin(admin: Pubkey, new_admin: Pubkey) -> Instruction {
+    let config = find_config_address().0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig { admin, config }.to_account_metas(None),
+        data: instruction::SetConfigAdmin { new_admin }.data(),
+    }
+}
+
+pub fn set_platform_fee(admin: Pubkey, fee_bps: u16, fee_treasury: Pubkey) -> Instruction {
+    let config = find_config_address().0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig { admin, config }.to_account_metas(None),
+        data: instruction::SetPlatformFee {
+            fee_bps,
+            fee_treasury,
+        }
+        .data(),
+    }
+}
+
+pub fn set_paused(admin: Pubkey, paused: bool) -> Instruction {
+    let config = find_config_address().0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateConfig { admin, config }.to_account_metas(None),
+        data: instruction::SetPaused { paused }.data(),
+    }
+}
+
+pub fn verify_organizer(admin: Pubkey, organizer: Pubkey, verified: bool) -> Instruction {
+    let config = find_config_address().0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::VerifyOrganizer {
+            admin,
+            config,
+            organizer,
+        }
+        .to_account_metas(None),
+        data: instruction::VerifyOrganizer { verified }.data(),
+    }
+}
+
+pub fn create_organizer(authority: Pubkey, name: String, uri: String) -> Instruction {
+    let config = find_config_address().0;
+    let organizer = find_organizer_address(&authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateOrganizer {
+            system_program: system_program::ID,
+            config,
+            authority,
+            organizer,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateOrganizer { name, uri }.data(),
+    }
+}
+
+pub fn update_organizer(authority: Pubkey, name: String, uri: String) -> Instruction {
+    let config = find_config_address().0;
+    let organizer = find_organizer_address(&authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateOrganizer {
+            config,
+            authority,
+            organizer,
+        }
+        .to_account_metas(None),
+        data: instruction::UpdateOrganizer { name, uri }.data(),
+    }
+}
+
+pub fn create_series(authority: Pubkey, series_base: Pubkey, name: String) -> Instruction {
+    let config = find_config_address().0;
+    let organizer = find_organizer_address(&authority).0;
+    let series = find_series_address(&organizer, &series_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateSeries {
+            system_program: system_program::ID,
+            config,
+            authority,
+            organizer,
+            series_base,
+            series,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateSeries { name }.data(),
+    }
+}
+
+pub fn create_organizer_event(
+    authority: Pubkey,
+    event_index: u32,
+    accepted_mint: Pubkey,
+    event_title: String,
+) -> Instruction {
+    let config = find_config_address().0;
+    let organizer = find_organizer_address(&authority).0;
+    let event_base = find_organizer_event_base_address(&organizer, event_index).0;
+    let event = find_event_address(&event_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateOrganizerEvent {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            organizer,
+            event_base,
+            event,
+            accepted_mint,
+            event_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateOrganizerEvent { event_title }.data(),
+    }
+}
+
+pub fn add_event_to_series(authority: Pubkey, series: Pubkey, event: Pubkey) -> Instruction {
+    let config = find_config_address().0;
+    let organizer = find_organizer_address(&authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::AddEventToSeries {
+            config,
+            authority,
+            organizer,
+            series,
+            event,
+        }
+        .to_account_metas(None),
+        data: instruction::AddEventToSeries {}.data(),
+    }
+}
+
+pub fn create_venue(
+    authority: Pubkey,
+    venue_base: Pubkey,
+    name: String,
+    location_uri: String,
+    capacity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let venue = find_venue_address(&venue_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateVenue {
+            system_program: system_program::ID,
+            config,
+            authority,
+            venue_base,
+            venue,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateVenue {
+            name,
+            location_uri,
+            capacity,
+        }
+        .data(),
+    }
+}
+
+pub fn set_event_venue(authority: Pubkey, event_base: Pubkey, venue: Pubkey) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetEventVenue {
+            config,
+            authority,
+            event_base,
+            event,
+            venue,
+        }
+        .to_account_metas(None),
+        data: instruction::SetEventVenue {}.data(),
+    }
+}
+
+pub fn set_event_paused(authority: Pubkey, event_base: Pubkey, paused: bool) -> Instruction {
+    let event = find_event_address(&event_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetEventPaused {
+            authority,
+            event_base,
+            event,
+        }
+        .to_account_metas(None),
+        data: instruction::SetEventPaused { paused }.data(),
+    }
+}
+
+pub fn update_event_schedule(
+    authority: Pubkey,
+    event_base: Pubkey,
+    start_time: i64,
+    end_time: i64,
+    timezone: String,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateEventSchedule {
+            config,
+            authority,
+            event_base,
+            event,
+        }
+        .to_account_metas(None),
+        data: instruction::UpdateEventSchedule {
+            start_time,
+            end_time,
+            timezone,
+        }
+        .data(),
+    }
+}
+
+pub fn update_event_details(
+    authority: Pubkey,
+    event_base: Pubkey,
+    location: String,
+    tags: Vec<String>,
+    metadata_uri: String,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::UpdateEventDetails {
+            config,
+            authority,
+            event_base,
+            event,
+        }
+        .to_account_metas(None),
+        data: instruction::UpdateEventDetails {
+            location,
+            tags,
+            metadata_uri,
+        }
+        .data(),
+    }
+}
+
+pub fn create_collaborator(
+    authority: Pubkey,
+    event_base: Pubkey,
+    collaborator_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateCollaborator {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            collaborator_base,
+            collaborator,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateCollaborator {}.data(),
+    }
+}
+
+pub fn delete_collaborator(
+    authority: Pubkey,
+    event_base: Pubkey,
+    collaborator_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DeleteCollaborator {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            collaborator_base,
+            collaborator,
+        }
+        .to_account_metas(None),
+        data: instruction::DeleteCollaborator {}.data(),
+    }
+}
+
+pub fn create_event_ticket(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_name: String,
+    ticket_symbol: String,
+    ticket_uri: String,
+    ticket_price: u32,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_metadata = find_metadata_address(&ticket_mint).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateEventTicket {
+            metadata_program: mpl_token_metadata::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_metadata,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateEventTicket {
+            ticket_name,
+            ticket_symbol,
+            ticket_uri,
+            ticket_price,
+            ticket_quantity,
+        }
+        .data(),
+    }
+}
+
+pub fn create_proof_of_attendance(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    poap_name: String,
+    poap_symbol: String,
+    poap_uri: String,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let attendance_mint = find_attendance_mint_address(&event, &event_ticket).0;
+    let attendance_metadata = find_metadata_address(&attendance_mint).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateProofOfAttendance {
+            metadata_program: mpl_token_metadata::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            attendance_mint,
+            attendance_metadata,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateProofOfAttendance {
+            poap_name,
+            poap_symbol,
+            poap_uri,
+        }
+        .data(),
+    }
+}
+
+pub fn set_ticket_auction(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    start_price: u32,
+    floor_price: u32,
+    start_time: i64,
+    decay_interval: i64,
+    decay_amount: u32,
+    with_rebates: bool,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetTicketAuction {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTicketAuction {
+            start_price,
+            floor_price,
+            start_time,
+            decay_interval,
+            decay_amount,
+            with_rebates,
+        }
+        .data(),
+    }
+}
+
+pub fn set_ticket_pricing_curve(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    pricing_curve: Option<PricingCurve>,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetTicketPricingCurve {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTicketPricingCurve { pricing_curve }.data(),
+    }
+}
+
+pub fn create_raffle(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    accepted_mint: Pubkey,
+    registration_start: i64,
+    registration_end: i64,
+    commitment: [u8; 32],
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+    let raffle_vault = find_raffle_vault_address(&raffle).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateRaffle {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+            accepted_mint,
+            raffle_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateRaffle {
+            registration_start,
+            registration_end,
+            commitment,
+        }
+        .data(),
+    }
+}
+
+pub fn create_waitlist(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    accepted_mint: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_vault = find_waitlist_vault_address(&waitlist).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateWaitlist {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            accepted_mint,
+            waitlist_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateWaitlist {}.data(),
+    }
+}
+
+pub fn create_seat_map(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    sections: u16,
+    rows: u16,
+    seats_per_row: u16,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let seat_map = find_seat_map_address(&event_ticket).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateSeatMap {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            seat_map,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateSeatMap {
+            sections,
+            rows,
+            seats_per_row,
+        }
+        .data(),
+    }
+}
+
+pub fn set_ticket_sessions(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    sessions: u8,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetTicketSessions {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTicketSessions { sessions }.data(),
+    }
+}
+
+pub fn set_ticket_reentry(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    allows_reentry: bool,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetTicketReentry {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTicketReentry { allows_reentry }.data(),
+    }
+}
+
+pub fn set_ticket_wallet_limit(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    max_per_wallet: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetTicketWalletLimit {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SetTicketWalletLimit { max_per_wallet }.data(),
+    }
+}
+
+pub fn create_promo(
+    authority: Pubkey,
+    event_base: Pubkey,
+    code_hash: [u8; 32],
+    discount_kind: DiscountKind,
+    discount_value: u32,
+    max_redemptions: u32,
+    event_ticket: Option<Pubkey>,
+    expires_at: Option<i64>,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let promo = find_promo_address(&event, &code_hash).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreatePromo {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            promo,
+        }
+        .to_account_metas(None),
+        data: instruction::CreatePromo {
+            code_hash,
+            discount_kind,
+            discount_value,
+            max_redemptions,
+            event_ticket,
+            expires_at,
+        }
+        .data(),
+    }
+}
+
+pub fn delete_promo(authority: Pubkey, event_base: Pubkey, code_hash: [u8; 32]) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let promo = find_promo_address(&event, &code_hash).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DeletePromo {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            promo,
+        }
+        .to_account_metas(None),
+        data: instruction::DeletePromo {}.data(),
+    }
+}
+
+pub fn create_affiliate(
+    authority: Pubkey,
+    event_base: Pubkey,
+    affiliate_base: Pubkey,
+    commission_bps: u16,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let affiliate = find_affiliate_address(&event, &affiliate_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CreateAffiliate {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            affiliate_base,
+            affiliate,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateAffiliate { commission_bps }.data(),
+    }
+}
+
+pub fn delete_affiliate(
+    authority: Pubkey,
+    event_base: Pubkey,
+    affiliate_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let affiliate = find_affiliate_address(&event, &affiliate_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DeleteAffiliate {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            affiliate_base,
+            affiliate,
+        }
+        .to_account_metas(None),
+        data: instruction::DeleteAffiliate {}.data(),
+    }
+}
+
+/// `authority` pays for the tickets, which are sent to the associated token
+/// account of `recipient`, created if it doesn't exist yet. Pass the buyer as
+/// recipient to buy for themselves.
+pub fn buy_tickets(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    recipient: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&recipient, &ticket_mint);
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &recipient).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::BuyTickets {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            fee_payer,
+            authority,
+            recipient,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            buyer_vault,
+            event_vault,
+            ticket_mint,
+            ticket_vault,
+            config,
+            fee_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::BuyTickets {
+            ticket_quantity,
+            promo_code: None,
+            affiliate_base: None,
+        }
+        .data(),
+    }
+}
+
+/// Same as `buy_tickets`, redeeming `promo_code` through its promo account.
+pub fn buy_tickets_with_promo(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    recipient: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+    promo_code: String,
+    ticket_quantity: u32,
+) -> Instruction {
+    let event = find_event_address(&event_base).0;
+    let promo = find_promo_address(&event, &hash(promo_code.as_bytes()).to_bytes()).0;
+    let mut instruction = buy_tickets(
+        fee_payer,
+        authority,
+        recipient,
+        event_base,
+        event_ticket_base,
+        buyer_vault,
+        fee_vault,
+        ticket_quantity,
+    );
+
+    instruction.accounts.push(AccountMeta::new(promo, false));
+    instruction.data = instruction::BuyTickets {
+        ticket_quantity,
+        promo_code: Some(promo_code),
+        affiliate_base: None,
+    }
+    .data();
+
+    instruction
+}
+
+/// Same as `buy_tickets`, paying the affiliate of `affiliate_base` its
+/// commission into `affiliate_vault`.
+pub fn buy_tickets_with_affiliate(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    recipient: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+    affiliate_base: Pubkey,
+    affiliate_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let event = find_event_address(&event_base).0;
+    let affiliate = find_affiliate_address(&event, &affiliate_base).0;
+    let mut instruction = buy_tickets(
+        fee_payer,
+        authority,
+        recipient,
+        event_base,
+        event_ticket_base,
+        buyer_vault,
+        fee_vault,
+        ticket_quantity,
+    );
+
+    instruction.accounts.extend([
+        AccountMeta::new(affiliate, false),
+        AccountMeta::new(affiliate_vault, false),
+    ]);
+    instruction.data = instruction::BuyTickets {
+        ticket_quantity,
+        promo_code: None,
+        affiliate_base: Some(affiliate_base),
+    }
+    .data();
+
+    instruction
+}
+
+pub fn buy_auction_tickets(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&authority, &ticket_mint);
+    let auction_receipt = find_auction_receipt_address(&event_ticket, &authority).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::BuyAuctionTickets {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            buyer_vault,
+            event_vault,
+            fee_vault,
+            ticket_mint,
+            ticket_vault,
+            auction_receipt,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::BuyAuctionTickets { ticket_quantity }.data(),
+    }
+}
+
+pub fn settle_auction(event_base: Pubkey, event_ticket_base: Pubkey) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SettleAuction {
+            config,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+        }
+        .to_account_metas(None),
+        data: instruction::SettleAuction {}.data(),
+    }
+}
+
+pub fn claim_auction_rebate(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let auction_receipt = find_auction_receipt_address(&event_ticket, &authority).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClaimAuctionRebate {
+            token_program: token::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            auction_receipt,
+            buyer_vault,
+            event_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimAuctionRebate {}.data(),
+    }
+}
+
+pub fn enter_raffle(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+    let raffle_entry = find_raffle_entry_address(&raffle, &authority).0;
+    let raffle_vault = find_raffle_vault_address(&raffle).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::EnterRaffle {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            config,
+            fee_payer,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+            raffle_entry,
+            buyer_vault,
+            raffle_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::EnterRaffle {}.data(),
+    }
+}
+
+pub fn seal_raffle(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SealRaffle {
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+        }
+        .to_account_metas(None),
+        data: instruction::SealRaffle {}.data(),
+    }
+}
+
+pub fn draw_raffle(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    fee_vault: Pubkey,
+    secret: [u8; 32],
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+    let raffle_vault = find_raffle_vault_address(&raffle).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::DrawRaffle {
+            token_program: token::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+            raffle_vault,
+            event_vault,
+            fee_vault,
+            slot_hashes: sysvar::slot_hashes::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::DrawRaffle { secret }.data(),
+    }
+}
+
+pub fn claim_raffle_entry(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+    let raffle_entry = find_raffle_entry_address(&raffle, &authority).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&authority, &ticket_mint);
+    let raffle_vault = find_raffle_vault_address(&raffle).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClaimRaffleEntry {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+            raffle_entry,
+            ticket_mint,
+            ticket_vault,
+            buyer_vault,
+            raffle_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimRaffleEntry {}.data(),
+    }
+}
+
+pub fn refund_raffle_entry(
+    entrant: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let raffle = find_raffle_address(&event_ticket).0;
+    let raffle_entry = find_raffle_entry_address(&raffle, &entrant).0;
+    let raffle_vault = find_raffle_vault_address(&raffle).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &entrant).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::RefundRaffleEntry {
+            token_program: token::ID,
+            config,
+            entrant,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            raffle,
+            raffle_entry,
+            buyer_vault,
+            raffle_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::RefundRaffleEntry {}.data(),
+    }
+}
+
+pub fn join_waitlist(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    waitlist_position: u64,
+    buyer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, waitlist_position).0;
+    let waitlist_vault = find_waitlist_vault_address(&waitlist).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&authority, &ticket_mint);
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::JoinWaitlist {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            waitlist_entry,
+            buyer_vault,
+            waitlist_vault,
+            ticket_mint,
+            ticket_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::JoinWaitlist {}.data(),
+    }
+}
+
+pub fn leave_waitlist(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    position: u64,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, position).0;
+    let waitlist_vault = find_waitlist_vault_address(&waitlist).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::LeaveWaitlist {
+            token_program: token::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            waitlist_entry,
+            buyer_vault,
+            waitlist_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::LeaveWaitlist {
+            _position: position,
+        }
+        .data(),
+    }
+}
+
+pub fn skip_waitlist_entry(
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    waitlist_position: u64,
+    owner: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, waitlist_position).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SkipWaitlistEntry {
+            config,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            waitlist_entry,
+            owner,
+        }
+        .to_account_metas(None),
+        data: instruction::SkipWaitlistEntry {}.data(),
+    }
+}
+
+pub fn release_ticket(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    waitlist_position: u64,
+    owner: Pubkey,
+    ticket_vault: Pubkey,
+    waitlist_ticket_vault: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, waitlist_position).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let waitlist_vault = find_waitlist_vault_address(&waitlist).0;
+    let event_vault = find_event_vault_address(&event).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ReleaseTicket {
+            token_program: token::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            waitlist_entry,
+            owner,
+            ticket_mint,
+            ticket_vault,
+            waitlist_ticket_vault,
+            buyer_vault,
+            waitlist_vault,
+            event_vault,
+            fee_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::ReleaseTicket {}.data(),
+    }
+}
+
+pub fn allocate_waitlist_entry(
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    waitlist_position: u64,
+    owner: Pubkey,
+    waitlist_ticket_vault: Pubkey,
+    fee_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let waitlist = find_waitlist_address(&event_ticket).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, waitlist_position).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let waitlist_vault = find_waitlist_vault_address(&waitlist).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::AllocateWaitlistEntry {
+            token_program: token::ID,
+            config,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            waitlist,
+            waitlist_entry,
+            owner,
+            ticket_mint,
+            waitlist_ticket_vault,
+            waitlist_vault,
+            event_vault,
+            fee_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::AllocateWaitlistEntry {}.data(),
+    }
+}
+
+pub fn buy_seats(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+    seat_reservation_base: Pubkey,
+    seat_ids: Vec<u32>,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&authority, &ticket_mint);
+    let seat_map = find_seat_map_address(&event_ticket).0;
+    let seat_reservation = find_seat_reservation_address(&event_ticket, &seat_reservation_base).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::BuySeats {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            buyer_vault,
+            event_vault,
+            fee_vault,
+            ticket_mint,
+            ticket_vault,
+            seat_map,
+            seat_reservation_base,
+            seat_reservation,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::BuySeats { seat_ids }.data(),
+    }
+}
+
+pub fn reserve_tickets(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_hold = find_ticket_hold_address(&event_ticket, &authority).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ReserveTickets {
+            system_program: system_program::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            ticket_hold,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::ReserveTickets { ticket_quantity }.data(),
+    }
+}
+
+pub fn complete_reservation(
+    fee_payer: Pubkey,
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer_vault: Pubkey,
+    fee_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let ticket_vault = get_associated_token_address(&authority, &ticket_mint);
+    let ticket_hold = find_ticket_hold_address(&event_ticket, &authority).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &authority).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CompleteReservation {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            authority,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            buyer_vault,
+            event_vault,
+            fee_vault,
+            ticket_mint,
+            ticket_vault,
+            ticket_hold,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::CompleteReservation {}.data(),
+    }
+}
+
+pub fn release_expired_hold(
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    owner: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_hold = find_ticket_hold_address(&event_ticket, &owner).0;
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &owner).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ReleaseExpiredHold {
+            config,
+            event_base,
+            event,
+            event_ticket_base,
+            event_ticket,
+            ticket_hold,
+            owner,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::ReleaseExpiredHold {}.data(),
+    }
+}
+
+pub fn issue_comp_tickets(
+    authority: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    recipient: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &authority).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let recipient_vault = get_associated_token_address(&recipient, &ticket_mint);
+    let wallet_purchase = find_wallet_purchase_address(&event_ticket, &recipient).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::IssueCompTickets {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            authority,
+            recipient,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            recipient_vault,
+            wallet_purchase,
+        }
+        .to_account_metas(None),
+        data: instruction::IssueCompTickets { ticket_quantity }.data(),
+    }
+}
+
+pub fn check_in(
+    fee_payer: Pubkey,
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckIn {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckIn { ticket_quantity }.data(),
+    }
+}
+
+/// The POAP is minted to the attendee's associated token account, which is
+/// created if it doesn't exist yet.
+pub fn check_in_with_attendance(
+    fee_payer: Pubkey,
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let attendance_mint = find_attendance_mint_address(&event, &event_ticket).0;
+    let attendance_vault = get_associated_token_address(&attendee, &attendance_mint);
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckInWithAttendance {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+            attendance_mint,
+            attendance_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckInWithAttendance { ticket_quantity }.data(),
+    }
+}
+
+pub fn check_in_seat(
+    fee_payer: Pubkey,
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+    seat_reservation_base: Pubkey,
+    seat_id: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let seat_reservation = find_seat_reservation_address(&event_ticket, &seat_reservation_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckInSeat {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+            seat_reservation_base,
+            seat_reservation,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckInSeat { seat_id }.data(),
+    }
+}
+
+pub fn check_in_pass(
+    fee_payer: Pubkey,
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+    session: u8,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let pass_usage = find_pass_usage_address(&event_ticket, &attendee).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckInPass {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            config,
+            fee_payer,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+            pass_usage,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckInPass {
+            session,
+            ticket_quantity,
+        }
+        .data(),
+    }
+}
+
+pub fn check_in_with_reentry(
+    fee_payer: Pubkey,
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+    ticket_quantity: u32,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let entry = find_entry_address(&event_ticket, &attendee).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckInWithReentry {
+            system_program: system_program::ID,
+            token_program: token::ID,
+            config,
+            fee_payer,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+            entry,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckInWithReentry { ticket_quantity }.data(),
+    }
+}
+
+pub fn check_out(
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let entry = find_entry_address(&event_ticket, &attendee).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::CheckOut {
+            config,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            entry,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckOut {}.data(),
+    }
+}
+
+pub fn re_enter(
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let entry = find_entry_address(&event_ticket, &attendee).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ReEnter {
+            config,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            entry,
+        }
+        .to_account_metas(None),
+        data: instruction::ReEnter {}.data(),
+    }
+}
+
+pub fn finalize_entry(
+    collaborator_base: Pubkey,
+    attendee: Pubkey,
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    ticket_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let collaborator = find_collaborator_address(&event, &collaborator_base).0;
+    let event_ticket = find_event_ticket_address(&event, &event_ticket_base).0;
+    let ticket_mint = find_ticket_mint_address(&event, &event_ticket).0;
+    let entry = find_entry_address(&event_ticket, &attendee).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::FinalizeEntry {
+            token_program: token::ID,
+            config,
+            collaborator_base,
+            attendee,
+            event_base,
+            event,
+            collaborator,
+            event_ticket_base,
+            event_ticket,
+            ticket_mint,
+            ticket_vault,
+            entry,
+        }
+        .to_account_metas(None),
+        data: instruction::FinalizeEntry {}.data(),
+    }
+}
+
+pub fn set_event_relayer(
+    authority: Pubkey,
+    event_base: Pubkey,
+    relayer: Option<Pubkey>,
+    relayer_fee: u64,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::SetEventRelayer {
+            config,
+            authority,
+            event_base,
+            event,
+        }
+        .to_account_metas(None),
+        data: instruction::SetEventRelayer {
+            relayer,
+            relayer_fee,
+        }
+        .data(),
+    }
+}
+
+pub fn claim_relayer_reimbursement(
+    relayer: Pubkey,
+    event_base: Pubkey,
+    relayer_vault: Pubkey,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::ClaimRelayerReimbursement {
+            token_program: token::ID,
+            config,
+            relayer,
+            event_base,
+            event,
+            event_vault,
+            relayer_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimRelayerReimbursement {}.data(),
+    }
+}
+
+pub fn withdraw(
+    authority: Pubkey,
+    event_base: Pubkey,
+    destination_vault: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let config = find_config_address().0;
+    let event = find_event_address(&event_base).0;
+    let event_vault = find_event_vault_address(&event).0;
+
+    Instruction {
+        program_id: ID,
+        accounts: accounts::Withdraw {
+            token_program: token::ID,
+            config,
+            authority,
+            event_base,
+            event,
+            event_vault,
+            destination_vault,
+        }
+        .to_account_metas(None),
+        data: instruction::Withdraw { amount }.data(),
+    }
+}
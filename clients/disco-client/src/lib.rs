@@ -0,0 +1,10 @@
+//! Rust client for the `disco` program: PDA derivation, instruction builders
+//! and account deserializers.
+
+#![allow(clippy::result_large_err, clippy::too_many_arguments)]
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use disco::ID;
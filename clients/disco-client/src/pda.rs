@@ -0,0 +1,176 @@
+//! PDA derivation helpers mirroring the seeds declared in the `disco` program.
+
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+
+use crate::ID;
+
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &ID)
+}
+
+pub fn find_organizer_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"organizer", authority.as_ref()], &ID)
+}
+
+pub fn find_organizer_event_base_address(organizer: &Pubkey, index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"organizer_event",
+            organizer.as_ref(),
+            index.to_le_bytes().as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_series_address(organizer: &Pubkey, series_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"series", organizer.as_ref(), series_base.as_ref()], &ID)
+}
+
+pub fn find_venue_address(venue_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"venue", venue_base.as_ref()], &ID)
+}
+
+pub fn find_event_address(event_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"event", event_base.as_ref()], &ID)
+}
+
+pub fn find_event_vault_address(event: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"event_vault", event.as_ref()], &ID)
+}
+
+pub fn find_collaborator_address(event: &Pubkey, collaborator_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"collaborator", event.as_ref(), collaborator_base.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_event_ticket_address(event: &Pubkey, event_ticket_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"event_ticket", event.as_ref(), event_ticket_base.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_ticket_mint_address(event: &Pubkey, event_ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"ticket_mint", event.as_ref(), event_ticket.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_attendance_mint_address(event: &Pubkey, event_ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"attendance_mint", event.as_ref(), event_ticket.as_ref()],
+        &ID,
+    )
+}
+
+/// Metaplex metadata account of a ticket or attendance mint.
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+}
+
+pub fn find_promo_address(event: &Pubkey, code_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"promo", event.as_ref(), code_hash.as_ref()], &ID)
+}
+
+pub fn find_affiliate_address(event: &Pubkey, affiliate_base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"affiliate", event.as_ref(), affiliate_base.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_auction_receipt_address(event_ticket: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"auction_receipt",
+            event_ticket.as_ref(),
+            authority.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_wallet_purchase_address(event_ticket: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"wallet_purchase", event_ticket.as_ref(), wallet.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_raffle_address(event_ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"raffle", event_ticket.as_ref()], &ID)
+}
+
+pub fn find_raffle_vault_address(raffle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"raffle_vault", raffle.as_ref()], &ID)
+}
+
+pub fn find_raffle_entry_address(raffle: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"raffle_entry", raffle.as_ref(), authority.as_ref()], &ID)
+}
+
+pub fn find_waitlist_address(event_ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"waitlist", event_ticket.as_ref()], &ID)
+}
+
+pub fn find_waitlist_vault_address(waitlist: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"waitlist_vault", waitlist.as_ref()], &ID)
+}
+
+pub fn find_waitlist_entry_address(waitlist: &Pubkey, position: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"waitlist_entry",
+            waitlist.as_ref(),
+            position.to_le_bytes().as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_seat_map_address(event_ticket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"seat_map", event_ticket.as_ref()], &ID)
+}
+
+pub fn find_seat_reservation_address(
+    event_ticket: &Pubkey,
+    seat_reservation_base: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"seat_reservation",
+            event_ticket.as_ref(),
+            seat_reservation_base.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_ticket_hold_address(event_ticket: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"ticket_hold", event_ticket.as_ref(), owner.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_pass_usage_address(event_ticket: &Pubkey, attendee: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"pass_usage", event_ticket.as_ref(), attendee.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_entry_address(event_ticket: &Pubkey, attendee: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"entry", event_ticket.as_ref(), attendee.as_ref()], &ID)
+}
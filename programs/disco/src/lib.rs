@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable, entrypoint::MAX_PERMITTED_DATA_INCREASE, sysvar,
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{burn, mint_to, transfer, Burn, Mint, MintTo, Token, TokenAccount, Transfer},
+    token::{
+        burn, freeze_account, mint_to, thaw_account, transfer, Burn, FreezeAccount, Mint, MintTo,
+        ThawAccount, Token, TokenAccount, Transfer,
+    },
 };
+use mpl_token_metadata::state::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
 
 declare_id!("EJQnbXhsLS92wsAXg1vPaZt88hfzmuhcqBVLQBn9h23x");
 
@@ -11,11 +18,188 @@ pub mod disco {
     use super::*;
 
     pub fn create_event(ctx: Context<CreateEvent>, event_title: String) -> Result<()> {
-        (*ctx.accounts.event).event_title = event_title.clone();
-        (*ctx.accounts.event).accepted_mint = ctx.accounts.accepted_mint.key();
-        (*ctx.accounts.event).authority = ctx.accounts.authority.key();
-        (*ctx.accounts.event).bump = *ctx.bumps.get("event").unwrap();
-        (*ctx.accounts.event).event_vault_bump = *ctx.bumps.get("event_vault").unwrap();
+        ctx.accounts.event.init(
+            event_title,
+            ctx.accounts.accepted_mint.key(),
+            ctx.accounts.authority.key(),
+            None,
+            *ctx.bumps.get("event").unwrap(),
+            *ctx.bumps.get("event_vault").unwrap(),
+        );
+
+        Ok(())
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_bps: u16,
+        fee_treasury: Pubkey,
+    ) -> Result<()> {
+        if fee_bps > 10_000 {
+            return Err(ErrorCode::InvalidFeeRate.into());
+        }
+
+        (*ctx.accounts.config).admin = ctx.accounts.admin.key();
+        (*ctx.accounts.config).fee_bps = fee_bps;
+        (*ctx.accounts.config).fee_treasury = fee_treasury;
+        (*ctx.accounts.config).paused = false;
+        (*ctx.accounts.config).bump = *ctx.bumps.get("config").unwrap();
+
+        Ok(())
+    }
+
+    pub fn set_config_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        (*ctx.accounts.config).admin = new_admin;
+
+        Ok(())
+    }
+
+    pub fn set_platform_fee(
+        ctx: Context<UpdateConfig>,
+        fee_bps: u16,
+        fee_treasury: Pubkey,
+    ) -> Result<()> {
+        if fee_bps > 10_000 {
+            return Err(ErrorCode::InvalidFeeRate.into());
+        }
+
+        (*ctx.accounts.config).fee_bps = fee_bps;
+        (*ctx.accounts.config).fee_treasury = fee_treasury;
+
+        Ok(())
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        (*ctx.accounts.config).paused = paused;
+
+        Ok(())
+    }
+
+    pub fn verify_organizer(ctx: Context<VerifyOrganizer>, verified: bool) -> Result<()> {
+        (*ctx.accounts.organizer).verified = verified;
+
+        Ok(())
+    }
+
+    pub fn create_organizer(
+        ctx: Context<CreateOrganizer>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        (*ctx.accounts.organizer).authority = ctx.accounts.authority.key();
+        (*ctx.accounts.organizer).name = name;
+        (*ctx.accounts.organizer).uri = uri;
+        (*ctx.accounts.organizer).verified = false;
+        (*ctx.accounts.organizer).event_count = 0;
+        (*ctx.accounts.organizer).bump = *ctx.bumps.get("organizer").unwrap();
+
+        Ok(())
+    }
+
+    pub fn update_organizer(
+        ctx: Context<UpdateOrganizer>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        (*ctx.accounts.organizer).name = name;
+        (*ctx.accounts.organizer).uri = uri;
+
+        Ok(())
+    }
+
+    pub fn create_series(ctx: Context<CreateSeries>, name: String) -> Result<()> {
+        (*ctx.accounts.series).organizer = ctx.accounts.organizer.key();
+        (*ctx.accounts.series).name = name;
+        (*ctx.accounts.series).event_count = 0;
+        (*ctx.accounts.series).bump = *ctx.bumps.get("series").unwrap();
+
+        Ok(())
+    }
+
+    pub fn create_organizer_event(
+        ctx: Context<CreateOrganizerEvent>,
+        event_title: String,
+    ) -> Result<()> {
+        ctx.accounts.event.init(
+            event_title,
+            ctx.accounts.accepted_mint.key(),
+            ctx.accounts.authority.key(),
+            Some(ctx.accounts.organizer.key()),
+            *ctx.bumps.get("event").unwrap(),
+            *ctx.bumps.get("event_vault").unwrap(),
+        );
+        (*ctx.accounts.organizer).event_count = ctx
+            .accounts
+            .organizer
+            .event_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn add_event_to_series(ctx: Context<AddEventToSeries>) -> Result<()> {
+        (*ctx.accounts.event).series = Some(ctx.accounts.series.key());
+        (*ctx.accounts.series).event_count = ctx
+            .accounts
+            .series
+            .event_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn create_venue(
+        ctx: Context<CreateVenue>,
+        name: String,
+        location_uri: String,
+        capacity: u32,
+    ) -> Result<()> {
+        (*ctx.accounts.venue).authority = ctx.accounts.authority.key();
+        (*ctx.accounts.venue).name = name;
+        (*ctx.accounts.venue).location_uri = location_uri;
+        (*ctx.accounts.venue).capacity = capacity;
+        (*ctx.accounts.venue).bump = *ctx.bumps.get("venue").unwrap();
+
+        Ok(())
+    }
+
+    pub fn set_event_venue(ctx: Context<SetEventVenue>) -> Result<()> {
+        (*ctx.accounts.event).venue = Some(ctx.accounts.venue.key());
+        (*ctx.accounts.event).total_capacity = ctx.accounts.venue.capacity;
+
+        Ok(())
+    }
+
+    pub fn set_event_paused(ctx: Context<SetEventPaused>, paused: bool) -> Result<()> {
+        (*ctx.accounts.event).paused = paused;
+
+        Ok(())
+    }
+
+    pub fn update_event_schedule(
+        ctx: Context<UpdateEventSchedule>,
+        start_time: i64,
+        end_time: i64,
+        timezone: String,
+    ) -> Result<()> {
+        (*ctx.accounts.event).start_time = start_time;
+        (*ctx.accounts.event).end_time = end_time;
+        (*ctx.accounts.event).timezone = timezone;
+
+        Ok(())
+    }
+
+    pub fn update_event_details(
+        ctx: Context<UpdateEventDetails>,
+        location: String,
+        tags: Vec<String>,
+        metadata_uri: String,
+    ) -> Result<()> {
+        (*ctx.accounts.event).location = location;
+        (*ctx.accounts.event).tags = tags;
+        (*ctx.accounts.event).metadata_uri = metadata_uri;
 
         Ok(())
     }
@@ -38,10 +222,26 @@ pub mod disco {
         ticket_price: u32,
         ticket_quantity: u32,
     ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
         (*ctx.accounts.event_ticket).price = ticket_price;
         (*ctx.accounts.event_ticket).quantity = ticket_quantity;
+        (*ctx.accounts.event).total_allocated = ctx
+            .accounts
+            .event
+            .total_allocated
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
         (*ctx.accounts.event_ticket).sold = 0;
         (*ctx.accounts.event_ticket).used = 0;
+        (*ctx.accounts.event_ticket).comps = 0;
+        (*ctx.accounts.event_ticket).waitlisted = 0;
+        (*ctx.accounts.event_ticket).held = 0;
+        (*ctx.accounts.event_ticket).auction = None;
+        (*ctx.accounts.event_ticket).pricing_curve = None;
+        (*ctx.accounts.event_ticket).max_per_wallet = 0;
         (*ctx.accounts.event_ticket).bump = *ctx.bumps.get("event_ticket").unwrap();
         (*ctx.accounts.event_ticket).ticket_mint_bump = *ctx.bumps.get("ticket_mint").unwrap();
         (*ctx.accounts.event_ticket).ticket_metadata_bump =
@@ -137,11 +337,310 @@ pub mod disco {
         Ok(())
     }
 
-    pub fn buy_tickets(ctx: Context<BuyTickets>, ticket_quantity: u32) -> Result<()> {
-        (*ctx.accounts.event_ticket).sold += ticket_quantity;
+    pub fn set_ticket_auction(
+        ctx: Context<SetTicketAuction>,
+        start_price: u32,
+        floor_price: u32,
+        start_time: i64,
+        decay_interval: i64,
+        decay_amount: u32,
+        with_rebates: bool,
+    ) -> Result<()> {
+        if floor_price > start_price || decay_interval <= 0 {
+            return Err(ErrorCode::InvalidAuctionSchedule.into());
+        }
+
+        (*ctx.accounts.event_ticket).auction = Some(DutchAuction {
+            start_price,
+            floor_price,
+            start_time,
+            decay_interval,
+            decay_amount,
+            with_rebates,
+            last_price: start_price,
+            clearing_price: None,
+            sold: 0,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_ticket_pricing_curve(
+        ctx: Context<SetTicketPricingCurve>,
+        pricing_curve: Option<PricingCurve>,
+    ) -> Result<()> {
+        if let Some(PricingCurve::Stepped { step_size: 0, .. }) = pricing_curve {
+            return Err(ErrorCode::InvalidPricingCurve.into());
+        }
+
+        (*ctx.accounts.event_ticket).pricing_curve = pricing_curve;
+
+        Ok(())
+    }
+
+    pub fn create_raffle(
+        ctx: Context<CreateRaffle>,
+        registration_start: i64,
+        registration_end: i64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        if registration_start >= registration_end {
+            return Err(ErrorCode::InvalidRaffleSchedule.into());
+        }
+
+        (*ctx.accounts.event_ticket).has_raffle = true;
+        (*ctx.accounts.raffle).registration_start = registration_start;
+        (*ctx.accounts.raffle).registration_end = registration_end;
+        (*ctx.accounts.raffle).commitment = commitment;
+        (*ctx.accounts.raffle).entries = 0;
+        (*ctx.accounts.raffle).winners = 0;
+        (*ctx.accounts.raffle).winning_offset = 0;
+        (*ctx.accounts.raffle).drawn = false;
+        (*ctx.accounts.raffle).bump = *ctx.bumps.get("raffle").unwrap();
+        (*ctx.accounts.raffle).raffle_vault_bump = *ctx.bumps.get("raffle_vault").unwrap();
+
+        Ok(())
+    }
+
+    pub fn create_waitlist(ctx: Context<CreateWaitlist>) -> Result<()> {
+        (*ctx.accounts.waitlist).head = 0;
+        (*ctx.accounts.waitlist).tail = 0;
+        (*ctx.accounts.waitlist).bump = *ctx.bumps.get("waitlist").unwrap();
+        (*ctx.accounts.waitlist).waitlist_vault_bump = *ctx.bumps.get("waitlist_vault").unwrap();
+
+        Ok(())
+    }
+
+    pub fn create_seat_map(
+        ctx: Context<CreateSeatMap>,
+        sections: u16,
+        rows: u16,
+        seats_per_row: u16,
+    ) -> Result<()> {
+        (*ctx.accounts.event_ticket).has_seat_map = true;
+        (*ctx.accounts.seat_map).sections = sections;
+        (*ctx.accounts.seat_map).rows = rows;
+        (*ctx.accounts.seat_map).seats_per_row = seats_per_row;
+        (*ctx.accounts.seat_map).taken =
+            vec![0; SeatMap::bitmap_len(sections, rows, seats_per_row)];
+        (*ctx.accounts.seat_map).bump = *ctx.bumps.get("seat_map").unwrap();
+
+        Ok(())
+    }
+
+    pub fn set_ticket_sessions(ctx: Context<SetTicketSessions>, sessions: u8) -> Result<()> {
+        // Passes can't have a POAP: it's minted by check_in_with_attendance,
+        // which passes don't go through, and minting it from check_in_pass
+        // would make every session carry the attendance accounts.
+        (*ctx.accounts.event_ticket).sessions = sessions;
+
+        Ok(())
+    }
+
+    pub fn set_ticket_reentry(ctx: Context<SetTicketReentry>, allows_reentry: bool) -> Result<()> {
+        (*ctx.accounts.event_ticket).allows_reentry = allows_reentry;
+
+        Ok(())
+    }
+
+    pub fn set_ticket_wallet_limit(
+        ctx: Context<SetTicketWalletLimit>,
+        max_per_wallet: u32,
+    ) -> Result<()> {
+        (*ctx.accounts.event_ticket).max_per_wallet = max_per_wallet;
+
+        Ok(())
+    }
+
+    pub fn create_promo(
+        ctx: Context<CreatePromo>,
+        code_hash: [u8; 32],
+        discount_kind: DiscountKind,
+        discount_value: u32,
+        max_redemptions: u32,
+        event_ticket: Option<Pubkey>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        if discount_kind == DiscountKind::Percentage && discount_value > 100 {
+            return Err(ErrorCode::InvalidPromoDiscount.into());
+        }
+
+        (*ctx.accounts.promo).code_hash = code_hash;
+        (*ctx.accounts.promo).discount_kind = discount_kind;
+        (*ctx.accounts.promo).discount_value = discount_value;
+        (*ctx.accounts.promo).max_redemptions = max_redemptions;
+        (*ctx.accounts.promo).redemptions = 0;
+        (*ctx.accounts.promo).event_ticket = event_ticket;
+        (*ctx.accounts.promo).expires_at = expires_at;
+        (*ctx.accounts.promo).bump = *ctx.bumps.get("promo").unwrap();
+
+        Ok(())
+    }
+
+    pub fn delete_promo(_ctx: Context<DeletePromo>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_affiliate(ctx: Context<CreateAffiliate>, commission_bps: u16) -> Result<()> {
+        if commission_bps > 10_000 {
+            return Err(ErrorCode::InvalidCommissionRate.into());
+        }
+
+        (*ctx.accounts.affiliate).authority = ctx.accounts.affiliate_base.key();
+        (*ctx.accounts.affiliate).commission_bps = commission_bps;
+        (*ctx.accounts.affiliate).tickets_sold = 0;
+        (*ctx.accounts.affiliate).commission_earned = 0;
+        (*ctx.accounts.affiliate).bump = *ctx.bumps.get("affiliate").unwrap();
+
+        Ok(())
+    }
+
+    pub fn delete_affiliate(_ctx: Context<DeleteAffiliate>) -> Result<()> {
+        Ok(())
+    }
+
+    // A promo code is redeemed by passing its preimage along with the promo
+    // account as a remaining account. A sale through an affiliate passes the
+    // affiliate and its vault after it.
+    pub fn buy_tickets<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyTickets<'info>>,
+        ticket_quantity: u32,
+        promo_code: Option<String>,
+        affiliate_base: Option<Pubkey>,
+    ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        let mut remaining_accounts = ctx.remaining_accounts.iter();
+        let mut price = ctx.accounts.event_ticket.total_price(ticket_quantity)?;
+
+        if let Some(promo_code) = promo_code {
+            let promo_info = remaining_accounts
+                .next()
+                .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+            let mut promo = Account::<Promo>::try_from(promo_info)?;
+            let code_hash = solana_program::hash::hash(promo_code.as_bytes()).to_bytes();
+            let promo_address = Pubkey::create_program_address(
+                &[
+                    b"promo".as_ref(),
+                    ctx.accounts.event.key().as_ref(),
+                    code_hash.as_ref(),
+                    &[promo.bump],
+                ],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidPromoCode)?;
+
+            if promo.key() != promo_address {
+                return Err(ErrorCode::InvalidPromoCode.into());
+            }
+
+            if let Some(event_ticket) = promo.event_ticket {
+                if event_ticket != ctx.accounts.event_ticket.key() {
+                    return Err(ErrorCode::PromoIsNotValidForThisTicket.into());
+                }
+            }
+
+            if let Some(expires_at) = promo.expires_at {
+                if Clock::get()?.unix_timestamp > expires_at {
+                    return Err(ErrorCode::PromoHasExpired.into());
+                }
+            }
+
+            // every discounted ticket counts as a redemption
+            promo.redemptions = promo
+                .redemptions
+                .checked_add(ticket_quantity)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            if promo.redemptions > promo.max_redemptions {
+                return Err(ErrorCode::PromoHasNoRedemptionsLeft.into());
+            }
+
+            price = promo.apply(price, ticket_quantity);
+            promo.exit(ctx.program_id)?;
+        }
+
+        let amount = u64::from(price);
+
+        // the limit applies to whoever receives the tickets, not to the payer
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            ticket_quantity,
+            amount,
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+
+        // the affiliate's commission comes out of the event's share
+        let affiliate = match affiliate_base {
+            Some(affiliate_base) => {
+                let affiliate_info = remaining_accounts
+                    .next()
+                    .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+                let affiliate_vault_info = remaining_accounts
+                    .next()
+                    .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+                let mut affiliate = Account::<Affiliate>::try_from(affiliate_info)?;
+                let affiliate_vault = Account::<TokenAccount>::try_from(affiliate_vault_info)?;
+                let affiliate_address = Pubkey::create_program_address(
+                    &[
+                        b"affiliate".as_ref(),
+                        ctx.accounts.event.key().as_ref(),
+                        affiliate_base.as_ref(),
+                        &[affiliate.bump],
+                    ],
+                    ctx.program_id,
+                )
+                .map_err(|_| ErrorCode::InvalidAffiliate)?;
+
+                if affiliate.key() != affiliate_address {
+                    return Err(ErrorCode::InvalidAffiliate.into());
+                }
+
+                if affiliate_vault.mint != ctx.accounts.event.accepted_mint
+                    || affiliate_vault.owner != affiliate.authority
+                {
+                    return Err(ErrorCode::InvalidAffiliateVault.into());
+                }
+
+                // taken on what's left after the platform fee, so the payout
+                // never reaches into funds escrowed by other sales
+                let commission = affiliate.commission(
+                    amount
+                        .checked_sub(ctx.accounts.config.fee(amount)?)
+                        .ok_or(ErrorCode::ArithmeticOverflow)?,
+                )?;
+
+                affiliate.tickets_sold = affiliate
+                    .tickets_sold
+                    .checked_add(ticket_quantity)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+                affiliate.commission_earned = affiliate
+                    .commission_earned
+                    .checked_add(commission)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+                Some((affiliate, affiliate_vault, commission))
+            }
+            None => None,
+        };
+
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
 
         // call transfer from authority to event vault
-        transfer(
+        let fee = ctx.accounts.config.collect_payment(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
@@ -150,21 +649,44 @@ pub mod disco {
                     authority: ctx.accounts.authority.to_account_info(),
                 },
             ),
-            ctx.accounts
-                .event_ticket
-                .price
-                .checked_mul(ticket_quantity.into())
-                .unwrap()
-                .into(),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount,
         )?;
 
-        // call mintTo instruction
         let seeds = &[
             b"event".as_ref(),
             ctx.accounts.event_base.to_account_info().key.as_ref(),
             &[ctx.accounts.event.bump],
         ];
 
+        // what the event keeps from the sale, which caps the relayer's fee
+        let mut revenue = amount
+            .checked_sub(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if let Some((affiliate, affiliate_vault, commission)) = affiliate {
+            revenue = revenue
+                .checked_sub(commission)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            // call transfer from event vault to affiliate vault
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.event_vault.to_account_info(),
+                        to: affiliate_vault.to_account_info(),
+                        authority: ctx.accounts.event.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                commission,
+            )?;
+
+            affiliate.exit(ctx.program_id)?;
+        }
+
+        // call mintTo instruction
         mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
@@ -178,49 +700,118 @@ pub mod disco {
             ticket_quantity.into(),
         )?;
 
+        ctx.accounts
+            .event
+            .accrue_relayer_fee(ctx.accounts.fee_payer.key, revenue)?;
+
         Ok(())
     }
 
-    pub fn check_in(ctx: Context<CheckIn>, ticket_quantity: u32) -> Result<()> {
-        if ctx.accounts.event_ticket.has_poap {
-            return Err(ErrorCode::CheckInIsOnlyAvailableForTicketsWithoutAttendance.into());
+    pub fn buy_auction_tickets(
+        ctx: Context<BuyAuctionTickets>,
+        ticket_quantity: u32,
+    ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
         }
 
-        (*ctx.accounts.event_ticket).used += ticket_quantity;
+        let mut auction = ctx.accounts.event_ticket.auction.unwrap();
+        let now = Clock::get()?.unix_timestamp;
 
-        burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    authority: ctx.accounts.attendee.to_account_info(),
-                    from: ctx.accounts.ticket_vault.to_account_info(),
-                    mint: ctx.accounts.ticket_mint.to_account_info(),
-                },
-            ),
-            ticket_quantity.into(),
+        if now < auction.start_time {
+            return Err(ErrorCode::AuctionHasNotStarted.into());
+        }
+
+        // once settled, whatever is left keeps selling at the clearing price
+        let settled = auction.clearing_price.is_some();
+        let price = auction
+            .clearing_price
+            .unwrap_or_else(|| auction.price_at(now));
+        let amount = price
+            .checked_mul(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        // with rebates, only the floor is sure to be kept by the event
+        let amount_paid = if auction.with_rebates && !settled {
+            auction
+                .floor_price
+                .checked_mul(ticket_quantity)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            amount
+        };
+
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            ticket_quantity,
+            amount_paid.into(),
+            *ctx.bumps.get("wallet_purchase").unwrap(),
         )?;
 
-        Ok(())
-    }
+        if !settled {
+            auction.last_price = price;
+            auction.sold = auction
+                .sold
+                .checked_add(ticket_quantity)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
 
-    pub fn check_in_with_attendance(
-        ctx: Context<CheckInWithAttendance>,
-        ticket_quantity: u32,
-    ) -> Result<()> {
-        (*ctx.accounts.event_ticket).used += ticket_quantity;
+        // the price can't clear below the floor, so that's the most a bidder
+        // can be owed until the auction settles
+        if auction.with_rebates && !settled {
+            let rebate = (price - auction.floor_price)
+                .checked_mul(ticket_quantity)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        burn(
+            (*ctx.accounts.event).rebates_owed = ctx
+                .accounts
+                .event
+                .rebates_owed
+                .checked_add(rebate.into())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        (*ctx.accounts.event_ticket).auction = Some(auction);
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.auction_receipt).quantity = ctx
+            .accounts
+            .auction_receipt
+            .quantity
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.auction_receipt).amount_paid = ctx
+            .accounts
+            .auction_receipt
+            .amount_paid
+            .checked_add(u64::from(amount))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.auction_receipt).bump = *ctx.bumps.get("auction_receipt").unwrap();
+
+        // call transfer from authority to event vault
+        let fee = ctx.accounts.config.collect_payment(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    authority: ctx.accounts.attendee.to_account_info(),
-                    from: ctx.accounts.ticket_vault.to_account_info(),
-                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
                 },
             ),
-            ticket_quantity.into(),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount.into(),
         )?;
 
+        // call mintTo instruction
         let seeds = &[
             b"event".as_ref(),
             ctx.accounts.event_base.to_account_info().key.as_ref(),
@@ -231,8 +822,8 @@ pub mod disco {
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
-                    mint: ctx.accounts.attendance_mint.to_account_info(),
-                    to: ctx.accounts.attendance_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.ticket_vault.to_account_info(),
                     authority: ctx.accounts.event.to_account_info(),
                 },
                 &[&seeds[..]],
@@ -240,51 +831,3857 @@ pub mod disco {
             ticket_quantity.into(),
         )?;
 
+        ctx.accounts.event.accrue_relayer_fee(
+            ctx.accounts.fee_payer.key,
+            u64::from(amount)
+                .checked_sub(fee)
+                .ok_or(ErrorCode::ArithmeticOverflow)?,
+        )?;
+
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(event_title: String)]
-pub struct CreateEvent<'info> {
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    /// CHECK: This is used only for generating the PDA.
-    pub event_base: UncheckedAccount<'info>,
-    #[account(
-        init,
-        payer = authority,
-        space = Event::SIZE,
-        seeds = [
-            b"event".as_ref(),
-            event_base.key().as_ref(),
-        ],
-        bump
-    )]
-    pub event: Account<'info, Event>,
-    pub accepted_mint: Account<'info, Mint>,
-    #[account(
-        init,
-        payer = authority,
-        token::authority = event,
-        token::mint = accepted_mint,
-        seeds = [
-            b"event_vault".as_ref(),
-            event.key().as_ref(),
-        ],
-        bump
-    )]
-    pub event_vault: Account<'info, TokenAccount>,
-}
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        let event_ticket = &ctx.accounts.event_ticket;
+        let mut auction = event_ticket.auction.unwrap();
 
-#[derive(Accounts)]
-pub struct CreateCollaborator<'info> {
-    pub system_program: Program<'info, System>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        if auction.clearing_price.is_some() {
+            return Err(ErrorCode::AuctionIsAlreadySettled.into());
+        }
+
+        let sold_out = event_ticket.available() == 0;
+        let at_floor = auction.price_at(Clock::get()?.unix_timestamp) == auction.floor_price;
+
+        if !sold_out && !at_floor {
+            return Err(ErrorCode::AuctionIsStillRunning.into());
+        }
+
+        auction.clearing_price = Some(auction.last_price);
+        (*ctx.accounts.event_ticket).auction = Some(auction);
+
+        if auction.with_rebates {
+            let released = u64::from(auction.last_price - auction.floor_price)
+                .checked_mul(auction.sold.into())
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            (*ctx.accounts.event).rebates_owed = ctx
+                .accounts
+                .event
+                .rebates_owed
+                .checked_sub(released)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_auction_rebate(ctx: Context<ClaimAuctionRebate>) -> Result<()> {
+        let auction = ctx.accounts.event_ticket.auction.unwrap();
+
+        if !auction.with_rebates {
+            return Err(ErrorCode::AuctionRebatesAreDisabled.into());
+        }
+
+        let clearing_price = match auction.clearing_price {
+            Some(clearing_price) => clearing_price,
+            None => return Err(ErrorCode::AuctionIsNotSettled.into()),
+        };
+
+        let rebate = u64::from(clearing_price)
+            .checked_mul(ctx.accounts.auction_receipt.quantity.into())
+            .and_then(|cost| ctx.accounts.auction_receipt.amount_paid.checked_sub(cost))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        (*ctx.accounts.event).rebates_owed = ctx
+            .accounts
+            .event
+            .rebates_owed
+            .checked_sub(rebate)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.event_vault.to_account_info(),
+                    to: ctx.accounts.buyer_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            rebate,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn enter_raffle(ctx: Context<EnterRaffle>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if now < ctx.accounts.raffle.registration_start
+            || now > ctx.accounts.raffle.registration_end
+        {
+            return Err(ErrorCode::RaffleRegistrationIsClosed.into());
+        }
+
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            1,
+            ctx.accounts.event_ticket.price.into(),
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+        (*ctx.accounts.raffle_entry).index = ctx.accounts.raffle.entries;
+        (*ctx.accounts.raffle_entry).amount = ctx.accounts.event_ticket.price.into();
+        (*ctx.accounts.raffle_entry).bump = *ctx.bumps.get("raffle_entry").unwrap();
+        (*ctx.accounts.raffle).entries = ctx
+            .accounts
+            .raffle
+            .entries
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // call transfer from authority to raffle vault
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_vault.to_account_info(),
+                    to: ctx.accounts.raffle_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            ctx.accounts.event_ticket.price.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn seal_raffle(ctx: Context<SealRaffle>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if now <= ctx.accounts.raffle.registration_end {
+            return Err(ErrorCode::RaffleRegistrationIsStillOpen.into());
+        }
+
+        if now > ctx.accounts.raffle.draw_deadline() {
+            return Err(ErrorCode::RaffleDrawDeadlineHasPassed.into());
+        }
+
+        // the hash of this slot isn't known yet, so the draw can't be steered
+        (*ctx.accounts.raffle).seal_slot = Clock::get()?.slot;
+
+        Ok(())
+    }
+
+    pub fn draw_raffle(ctx: Context<DrawRaffle>, secret: [u8; 32]) -> Result<()> {
+        let raffle = &ctx.accounts.raffle;
+
+        if Clock::get()?.unix_timestamp > raffle.draw_deadline() {
+            return Err(ErrorCode::RaffleDrawDeadlineHasPassed.into());
+        }
+
+        if solana_program::hash::hash(&secret).to_bytes() != raffle.commitment {
+            return Err(ErrorCode::InvalidRaffleSecret.into());
+        }
+
+        let slot_hash = Raffle::slot_hash(&ctx.accounts.slot_hashes, raffle.seal_slot)?;
+        let event_ticket = &ctx.accounts.event_ticket;
+        let winners = raffle.entries.min(event_ticket.available());
+        let seed =
+            solana_program::hash::hashv(&[&secret, &slot_hash, raffle.key().as_ref()]).to_bytes();
+        let winning_offset = if raffle.entries == 0 {
+            0
+        } else {
+            u32::from_le_bytes(seed[..4].try_into().unwrap()) % raffle.entries
+        };
+        let amount = u64::from(event_ticket.price)
+            .checked_mul(winners.into())
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        (*ctx.accounts.raffle).winners = winners;
+        (*ctx.accounts.raffle).winning_offset = winning_offset;
+        (*ctx.accounts.raffle).drawn = true;
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(winners)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(winners)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // call transfer from raffle vault to event vault
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        ctx.accounts.config.collect_payment(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.raffle_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn claim_raffle_entry(ctx: Context<ClaimRaffleEntry>) -> Result<()> {
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        if ctx
+            .accounts
+            .raffle
+            .is_winner(ctx.accounts.raffle_entry.index)
+        {
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.ticket_mint.to_account_info(),
+                        to: ctx.accounts.ticket_vault.to_account_info(),
+                        authority: ctx.accounts.event.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                1,
+            )?;
+        } else {
+            ctx.accounts
+                .wallet_purchase
+                .release(1, ctx.accounts.raffle_entry.amount)?;
+
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.raffle_vault.to_account_info(),
+                        to: ctx.accounts.buyer_vault.to_account_info(),
+                        authority: ctx.accounts.event.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                ctx.accounts.raffle_entry.amount,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn refund_raffle_entry(ctx: Context<RefundRaffleEntry>) -> Result<()> {
+        if Clock::get()?.unix_timestamp <= ctx.accounts.raffle.draw_deadline() {
+            return Err(ErrorCode::RaffleDrawDeadlineHasNotPassed.into());
+        }
+
+        ctx.accounts
+            .wallet_purchase
+            .release(1, ctx.accounts.raffle_entry.amount)?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.raffle_vault.to_account_info(),
+                    to: ctx.accounts.buyer_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ctx.accounts.raffle_entry.amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+        let amount = ctx.accounts.event_ticket.total_price(1)?;
+
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            1,
+            amount.into(),
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+
+        (*ctx.accounts.waitlist_entry).owner = ctx.accounts.authority.key();
+        (*ctx.accounts.waitlist_entry).ticket_vault = ctx.accounts.ticket_vault.key();
+        (*ctx.accounts.waitlist_entry).amount = amount.into();
+        (*ctx.accounts.waitlist_entry).bump = *ctx.bumps.get("waitlist_entry").unwrap();
+        (*ctx.accounts.waitlist).tail = ctx
+            .accounts
+            .waitlist
+            .tail
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event_ticket).waitlisted = ctx
+            .accounts
+            .event_ticket
+            .waitlisted
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // call transfer from authority to waitlist vault
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_vault.to_account_info(),
+                    to: ctx.accounts.waitlist_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn leave_waitlist(ctx: Context<LeaveWaitlist>, _position: u64) -> Result<()> {
+        let amount = ctx.accounts.waitlist_entry.amount;
+
+        ctx.accounts.wallet_purchase.release(1, amount)?;
+        (*ctx.accounts.waitlist_entry).amount = 0;
+        (*ctx.accounts.event_ticket).waitlisted = ctx
+            .accounts
+            .event_ticket
+            .waitlisted
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.waitlist_vault.to_account_info(),
+                    to: ctx.accounts.buyer_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn skip_waitlist_entry(ctx: Context<SkipWaitlistEntry>) -> Result<()> {
+        (*ctx.accounts.waitlist).head = ctx
+            .accounts
+            .waitlist
+            .head
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn release_ticket(ctx: Context<ReleaseTicket>) -> Result<()> {
+        let amount = ctx.accounts.waitlist_entry.amount;
+        let price_paid = ctx.accounts.wallet_purchase.price_paid()?;
+        let refund = price_paid.min(amount);
+
+        ctx.accounts.wallet_purchase.release(1, price_paid)?;
+
+        (*ctx.accounts.waitlist).head = ctx
+            .accounts
+            .waitlist
+            .head
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event_ticket).waitlisted = ctx
+            .accounts
+            .event_ticket
+            .waitlisted
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: ctx.accounts.authority.to_account_info(),
+                    from: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.waitlist_ticket_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            1,
+        )?;
+
+        // settle the waitlisted payment and refund the releaser from the event
+        // vault, never more than they paid, and minus the platform fee like
+        // on any sale
+        ctx.accounts.config.collect_payment(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.waitlist_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount,
+        )?;
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.event_vault.to_account_info(),
+                    to: ctx.accounts.buyer_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            refund
+                .checked_sub(ctx.accounts.config.fee(refund)?)
+                .ok_or(ErrorCode::ArithmeticOverflow)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn allocate_waitlist_entry(ctx: Context<AllocateWaitlistEntry>) -> Result<()> {
+        (*ctx.accounts.waitlist).head = ctx
+            .accounts
+            .waitlist
+            .head
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event_ticket).waitlisted = ctx
+            .accounts
+            .event_ticket
+            .waitlisted
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.waitlist_ticket_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            1,
+        )?;
+
+        ctx.accounts.config.collect_payment(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.waitlist_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ctx.accounts.fee_vault.to_account_info(),
+            ctx.accounts.waitlist_entry.amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn buy_seats(ctx: Context<BuySeats>, seat_ids: Vec<u32>) -> Result<()> {
+        if seat_ids.is_empty() || seat_ids.len() > SeatReservation::MAX_SEATS {
+            return Err(ErrorCode::InvalidSeatQuantity.into());
+        }
+
+        let ticket_quantity = seat_ids.len() as u32;
+
+        if ctx.accounts.event_ticket.available() < ticket_quantity {
+            return Err(ErrorCode::NotEnoughTicketsAvailable.into());
+        }
+
+        let amount = ctx.accounts.event_ticket.total_price(ticket_quantity)?;
+
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            ticket_quantity,
+            amount.into(),
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+
+        for seat_id in seat_ids.iter() {
+            (*ctx.accounts.seat_map).take(*seat_id)?;
+        }
+
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.seat_reservation).owner = ctx.accounts.authority.key();
+        (*ctx.accounts.seat_reservation).seats = seat_ids
+            .into_iter()
+            .map(|seat_id| ReservedSeat {
+                seat_id,
+                checked_in: false,
+            })
+            .collect();
+        (*ctx.accounts.seat_reservation).bump = *ctx.bumps.get("seat_reservation").unwrap();
+
+        // call transfer from authority to event vault
+        let fee = ctx.accounts.config.collect_payment(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount.into(),
+        )?;
+
+        // call mintTo instruction
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.ticket_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        ctx.accounts.event.accrue_relayer_fee(
+            ctx.accounts.fee_payer.key,
+            u64::from(amount)
+                .checked_sub(fee)
+                .ok_or(ErrorCode::ArithmeticOverflow)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn reserve_tickets(ctx: Context<ReserveTickets>, ticket_quantity: u32) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        ctx.accounts.wallet_purchase.hold(
+            &ctx.accounts.event_ticket,
+            ticket_quantity,
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+
+        (*ctx.accounts.event_ticket).held = ctx
+            .accounts
+            .event_ticket
+            .held
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.ticket_hold).owner = ctx.accounts.authority.key();
+        (*ctx.accounts.ticket_hold).quantity = ticket_quantity;
+        (*ctx.accounts.ticket_hold).expires_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(TicketHold::DURATION)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.ticket_hold).bump = *ctx.bumps.get("ticket_hold").unwrap();
+
+        Ok(())
+    }
+
+    pub fn complete_reservation(ctx: Context<CompleteReservation>) -> Result<()> {
+        if Clock::get()?.unix_timestamp > ctx.accounts.ticket_hold.expires_at {
+            return Err(ErrorCode::TicketHoldHasExpired.into());
+        }
+
+        let ticket_quantity = ctx.accounts.ticket_hold.quantity;
+        let amount = ctx.accounts.event_ticket.total_price(ticket_quantity)?;
+
+        ctx.accounts.wallet_purchase.release_hold(ticket_quantity)?;
+        ctx.accounts.wallet_purchase.record(
+            &ctx.accounts.event_ticket,
+            ticket_quantity,
+            amount.into(),
+            *ctx.bumps.get("wallet_purchase").unwrap(),
+        )?;
+
+        (*ctx.accounts.event_ticket).held = ctx
+            .accounts
+            .event_ticket
+            .held
+            .checked_sub(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event_ticket).sold = ctx
+            .accounts
+            .event_ticket
+            .sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (*ctx.accounts.event).total_sold = ctx
+            .accounts
+            .event
+            .total_sold
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // call transfer from authority to event vault
+        let fee = ctx.accounts.config.collect_payment(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_vault.to_account_info(),
+                    to: ctx.accounts.event_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            ctx.accounts.fee_vault.to_account_info(),
+            amount.into(),
+        )?;
+
+        // call mintTo instruction
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.ticket_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        ctx.accounts.event.accrue_relayer_fee(
+            ctx.accounts.fee_payer.key,
+            u64::from(amount)
+                .checked_sub(fee)
+                .ok_or(ErrorCode::ArithmeticOverflow)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn release_expired_hold(ctx: Context<ReleaseExpiredHold>) -> Result<()> {
+        if Clock::get()?.unix_timestamp <= ctx.accounts.ticket_hold.expires_at {
+            return Err(ErrorCode::TicketHoldHasNotExpired.into());
+        }
+
+        (*ctx.accounts.event_ticket).held = ctx
+            .accounts
+            .event_ticket
+            .held
+            .checked_sub(ctx.accounts.ticket_hold.quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts
+            .wallet_purchase
+            .release_hold(ctx.accounts.ticket_hold.quantity)?;
+
+        Ok(())
+    }
+
+    pub fn issue_comp_tickets(ctx: Context<IssueCompTickets>, ticket_quantity: u32) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        (*ctx.accounts.event_ticket).comps = ctx
+            .accounts
+            .event_ticket
+            .comps
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts
+            .wallet_purchase
+            .record_comps(ticket_quantity, *ctx.bumps.get("wallet_purchase").unwrap())?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.recipient_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn check_in(ctx: Context<CheckIn>, ticket_quantity: u32) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        if ctx.accounts.event_ticket.has_poap {
+            return Err(ErrorCode::CheckInIsOnlyAvailableForTicketsWithoutAttendance.into());
+        }
+
+        (*ctx.accounts.event_ticket).used = ctx
+            .accounts
+            .event_ticket
+            .used
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: ctx.accounts.attendee.to_account_info(),
+                    from: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                },
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn check_in_with_attendance(
+        ctx: Context<CheckInWithAttendance>,
+        ticket_quantity: u32,
+    ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        (*ctx.accounts.event_ticket).used = ctx
+            .accounts
+            .event_ticket
+            .used
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: ctx.accounts.attendee.to_account_info(),
+                    from: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                },
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.attendance_mint.to_account_info(),
+                    to: ctx.accounts.attendance_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            ticket_quantity.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn check_in_seat(ctx: Context<CheckInSeat>, seat_id: u32) -> Result<()> {
+        if ctx.accounts.event_ticket.has_poap {
+            return Err(ErrorCode::CheckInIsOnlyAvailableForTicketsWithoutAttendance.into());
+        }
+
+        let seat = match (*ctx.accounts.seat_reservation)
+            .seats
+            .iter_mut()
+            .find(|seat| seat.seat_id == seat_id)
+        {
+            Some(seat) => seat,
+            None => return Err(ErrorCode::SeatIsNotInReservation.into()),
+        };
+
+        if seat.checked_in {
+            return Err(ErrorCode::SeatIsAlreadyCheckedIn.into());
+        }
+
+        seat.checked_in = true;
+        (*ctx.accounts.event_ticket).used = ctx
+            .accounts
+            .event_ticket
+            .used
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: ctx.accounts.attendee.to_account_info(),
+                    from: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn check_in_pass(
+        ctx: Context<CheckInPass>,
+        session: u8,
+        ticket_quantity: u32,
+    ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        let sessions = ctx.accounts.event_ticket.sessions;
+
+        if session >= sessions {
+            return Err(ErrorCode::InvalidSession.into());
+        }
+
+        if ctx.accounts.pass_usage.sessions_used.is_empty() {
+            (*ctx.accounts.pass_usage).owner = ctx.accounts.attendee.key();
+            (*ctx.accounts.pass_usage).sessions_used = vec![0; sessions.into()];
+            (*ctx.accounts.pass_usage).bump = *ctx.bumps.get("pass_usage").unwrap();
+        }
+
+        let last_session = usize::from(sessions - 1);
+        let checked_in = ctx.accounts.pass_usage.sessions_used[usize::from(session)]
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // passes checked in to the last session have already been burned
+        let passes_held = ctx
+            .accounts
+            .ticket_vault
+            .amount
+            .checked_add(ctx.accounts.pass_usage.sessions_used[last_session].into())
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if u64::from(checked_in) > passes_held {
+            return Err(ErrorCode::NotEnoughTicketsToCheckIn.into());
+        }
+
+        (*ctx.accounts.pass_usage).sessions_used[usize::from(session)] = checked_in;
+
+        let new_passes = checked_in.saturating_sub(ctx.accounts.pass_usage.passes);
+
+        if new_passes > 0 {
+            (*ctx.accounts.pass_usage).passes = checked_in;
+            (*ctx.accounts.event_ticket).used = ctx
+                .accounts
+                .event_ticket
+                .used
+                .checked_add(new_passes)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        if usize::from(session) == last_session {
+            // the last session uses the passes up, so they are burned and
+            // whatever is left in the vault is unlocked again
+            if ctx.accounts.ticket_vault.is_frozen() {
+                thaw_account(CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    ThawAccount {
+                        account: ctx.accounts.ticket_vault.to_account_info(),
+                        mint: ctx.accounts.ticket_mint.to_account_info(),
+                        authority: ctx.accounts.event.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ))?;
+            }
+
+            burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        authority: ctx.accounts.attendee.to_account_info(),
+                        from: ctx.accounts.ticket_vault.to_account_info(),
+                        mint: ctx.accounts.ticket_mint.to_account_info(),
+                    },
+                ),
+                ticket_quantity.into(),
+            )?;
+        } else if !ctx.accounts.ticket_vault.is_frozen() {
+            // passes stay frozen between sessions, so one can't be handed to
+            // another wallet and checked in twice for the same session
+            freeze_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                FreezeAccount {
+                    account: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check_in_with_reentry(
+        ctx: Context<CheckInWithReentry>,
+        ticket_quantity: u32,
+    ) -> Result<()> {
+        if ticket_quantity == 0 {
+            return Err(ErrorCode::InvalidQuantity.into());
+        }
+
+        if u64::from(ticket_quantity) > ctx.accounts.ticket_vault.amount {
+            return Err(ErrorCode::NotEnoughTicketsToCheckIn.into());
+        }
+
+        (*ctx.accounts.entry).owner = ctx.accounts.attendee.key();
+        (*ctx.accounts.entry).quantity = ticket_quantity;
+        (*ctx.accounts.entry).inside = true;
+        (*ctx.accounts.entry).entered_at = Clock::get()?.unix_timestamp;
+        (*ctx.accounts.entry).exited_at = 0;
+        (*ctx.accounts.entry).bump = *ctx.bumps.get("entry").unwrap();
+        (*ctx.accounts.event_ticket).used = ctx
+            .accounts
+            .event_ticket
+            .used
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // the tickets stay frozen in the vault until the entry is finalized,
+        // so they can't be handed to someone else while the attendee is
+        // inside or out for a break
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.ticket_vault.to_account_info(),
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                authority: ctx.accounts.event.to_account_info(),
+            },
+            &[&seeds[..]],
+        ))?;
+
+        Ok(())
+    }
+
+    pub fn check_out(ctx: Context<CheckOut>) -> Result<()> {
+        if !ctx.accounts.entry.inside {
+            return Err(ErrorCode::AttendeeIsNotInside.into());
+        }
+
+        (*ctx.accounts.entry).inside = false;
+        (*ctx.accounts.entry).exited_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn re_enter(ctx: Context<ReEnter>) -> Result<()> {
+        if ctx.accounts.entry.inside {
+            return Err(ErrorCode::AttendeeIsAlreadyInside.into());
+        }
+
+        (*ctx.accounts.entry).inside = true;
+        (*ctx.accounts.entry).entered_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn finalize_entry(ctx: Context<FinalizeEntry>) -> Result<()> {
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.ticket_vault.to_account_info(),
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                authority: ctx.accounts.event.to_account_info(),
+            },
+            &[&seeds[..]],
+        ))?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: ctx.accounts.attendee.to_account_info(),
+                    from: ctx.accounts.ticket_vault.to_account_info(),
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                },
+            ),
+            ctx.accounts.entry.quantity.into(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn set_event_relayer(
+        ctx: Context<SetEventRelayer>,
+        relayer: Option<Pubkey>,
+        relayer_fee: u64,
+    ) -> Result<()> {
+        if ctx.accounts.event.relayer != relayer && ctx.accounts.event.relayer_owed > 0 {
+            return Err(ErrorCode::RelayerHasUnclaimedReimbursements.into());
+        }
+
+        (*ctx.accounts.event).relayer = relayer;
+        (*ctx.accounts.event).relayer_fee = relayer_fee;
+
+        Ok(())
+    }
+
+    pub fn claim_relayer_reimbursement(ctx: Context<ClaimRelayerReimbursement>) -> Result<()> {
+        let amount = ctx.accounts.event.relayer_owed;
+
+        (*ctx.accounts.event).relayer_owed = 0;
+
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.event_vault.to_account_info(),
+                    to: ctx.accounts.relayer_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let seeds = &[
+            b"event".as_ref(),
+            ctx.accounts.event_base.to_account_info().key.as_ref(),
+            &[ctx.accounts.event.bump],
+        ];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.event_vault.to_account_info(),
+                    to: ctx.accounts.destination_vault.to_account_info(),
+                    authority: ctx.accounts.event.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(event_title: String)]
+pub struct CreateEvent<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Event::size(&event_title),
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump,
+        constraint = event_title.len() <= Event::MAX_TITLE_LENGTH @ ErrorCode::EventTitleTooLong,
+        constraint = Event::is_valid_title(&event_title) @ ErrorCode::InvalidEventTitle
+    )]
+    pub event: Account<'info, Event>,
+    pub accepted_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::authority = event,
+        token::mint = accepted_mint,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::OnlyUpgradeAuthorityCanInitializeConfig
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SIZE,
+        seeds = [b"config".as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ ErrorCode::OnlyConfigAdminCanUpdateConfig
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyOrganizer<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ ErrorCode::OnlyConfigAdminCanVerifyOrganizers
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub organizer: Account<'info, Organizer>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, uri: String)]
+pub struct CreateOrganizer<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Organizer::SIZE,
+        seeds = [
+            b"organizer".as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+        constraint = name.len() <= Organizer::MAX_NAME_LENGTH @ ErrorCode::NameTooLong,
+        constraint = Event::is_valid_title(&name) @ ErrorCode::InvalidName,
+        constraint = uri.len() <= Organizer::MAX_URI_LENGTH @ ErrorCode::UriTooLong
+    )]
+    pub organizer: Account<'info, Organizer>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, uri: String)]
+pub struct UpdateOrganizer<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"organizer".as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = organizer.bump,
+        constraint = name.len() <= Organizer::MAX_NAME_LENGTH @ ErrorCode::NameTooLong,
+        constraint = Event::is_valid_title(&name) @ ErrorCode::InvalidName,
+        constraint = uri.len() <= Organizer::MAX_URI_LENGTH @ ErrorCode::UriTooLong
+    )]
+    pub organizer: Account<'info, Organizer>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateSeries<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [
+            b"organizer".as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = organizer.bump
+    )]
+    pub organizer: Account<'info, Organizer>,
+    /// CHECK: This is used only for generating the PDA.
+    pub series_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Series::SIZE,
+        seeds = [
+            b"series".as_ref(),
+            organizer.key().as_ref(),
+            series_base.key().as_ref(),
+        ],
+        bump,
+        constraint = name.len() <= Series::MAX_NAME_LENGTH @ ErrorCode::NameTooLong,
+        constraint = Event::is_valid_title(&name) @ ErrorCode::InvalidName
+    )]
+    pub series: Account<'info, Series>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_title: String)]
+pub struct CreateOrganizerEvent<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"organizer".as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = organizer.bump
+    )]
+    pub organizer: Account<'info, Organizer>,
+    /// CHECK: This is used only for generating the PDA, derived from the organizer's event count.
+    #[account(
+        seeds = [
+            b"organizer_event".as_ref(),
+            organizer.key().as_ref(),
+            organizer.event_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Event::size(&event_title),
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump,
+        constraint = event_title.len() <= Event::MAX_TITLE_LENGTH @ ErrorCode::EventTitleTooLong,
+        constraint = Event::is_valid_title(&event_title) @ ErrorCode::InvalidEventTitle
+    )]
+    pub event: Account<'info, Event>,
+    pub accepted_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::authority = event,
+        token::mint = accepted_mint,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AddEventToSeries<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [
+            b"organizer".as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = organizer.bump
+    )]
+    pub organizer: Account<'info, Organizer>,
+    #[account(
+        mut,
+        constraint = series.organizer == organizer.key() @ ErrorCode::SeriesBelongsToAnotherOrganizer
+    )]
+    pub series: Account<'info, Series>,
+    #[account(
+        mut,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanAddToSeries,
+        constraint = event.organizer == Some(organizer.key()) @ ErrorCode::EventBelongsToAnotherOrganizer,
+        constraint = event.series.is_none() @ ErrorCode::EventAlreadyInSeries,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, location_uri: String, capacity: u32)]
+pub struct CreateVenue<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub venue_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Venue::SIZE,
+        seeds = [
+            b"venue".as_ref(),
+            venue_base.key().as_ref(),
+        ],
+        bump,
+        constraint = name.len() <= Venue::MAX_NAME_LENGTH @ ErrorCode::NameTooLong,
+        constraint = location_uri.len() <= Venue::MAX_LOCATION_URI_LENGTH @ ErrorCode::UriTooLong,
+        constraint = capacity > 0 @ ErrorCode::InvalidVenueCapacity
+    )]
+    pub venue: Account<'info, Venue>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventVenue<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetVenue,
+        constraint = event.total_allocated <= venue.capacity @ ErrorCode::VenueCapacityExceeded,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
+        constraint = venue.authority == authority.key() @ ErrorCode::OnlyVenueAuthorityCanHostEvents
+    )]
+    pub venue: Account<'info, Venue>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventPaused<'info> {
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanPauseEvent
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(start_time: i64, end_time: i64, timezone: String)]
+pub struct UpdateEventSchedule<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanUpdateEvent,
+        constraint = !event.paused @ ErrorCode::EventPaused,
+        constraint = Event::is_valid_schedule(start_time, end_time) @ ErrorCode::InvalidEventSchedule,
+        constraint = timezone.len() <= Event::MAX_TIMEZONE_LENGTH @ ErrorCode::TimezoneTooLong,
+        constraint = Event::is_valid_timezone(&timezone) @ ErrorCode::InvalidTimezone
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+#[instruction(location: String, tags: Vec<String>, metadata_uri: String)]
+pub struct UpdateEventDetails<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanUpdateEvent,
+        constraint = !event.paused @ ErrorCode::EventPaused,
+        constraint = location.len() <= Event::MAX_LOCATION_LENGTH @ ErrorCode::LocationTooLong,
+        constraint = Event::is_valid_location(&location) @ ErrorCode::InvalidLocation,
+        constraint = tags.len() <= Event::MAX_TAGS @ ErrorCode::TooManyTags,
+        constraint = tags.iter().all(|tag| Event::is_valid_tag(tag)) @ ErrorCode::InvalidTag,
+        constraint = metadata_uri.len() <= Event::MAX_METADATA_URI_LENGTH @ ErrorCode::MetadataUriTooLong,
+        constraint = Event::is_valid_metadata_uri(&metadata_uri) @ ErrorCode::InvalidMetadataUri
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+pub struct CreateCollaborator<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreateCollaborators,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This account is used only as a base for derivation
+    pub collaborator_base: UncheckedAccount<'info>,
+    /// CHECK: This account is created in this instruction
+    #[account(
+        init,
+        space = Collaborator::SIZE,
+        payer = authority,
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteCollaborator<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanDeleteCollaborators,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This account is used only as a base for derivation
+    pub collaborator_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    ticket_name: String,
+    ticket_symbol: String,
+    ticket_uri: String,
+    ticket_price: u32,
+    ticket_quantity: u32,
+)]
+pub struct CreateEventTicket<'info> {
+    /// CHECK: this is verified through an address constraint
+    #[account(address = mpl_token_metadata::ID, executable)]
+    pub metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.can_allocate(ticket_quantity) @ ErrorCode::VenueCapacityExceeded,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = EventTicket::SIZE,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump,
+        constraint = ticket_name.len() <= MAX_NAME_LENGTH @ ErrorCode::TicketNameTooLong,
+        constraint = EventTicket::is_valid_name(&ticket_name) @ ErrorCode::InvalidTicketName,
+        constraint = ticket_symbol.len() <= MAX_SYMBOL_LENGTH @ ErrorCode::TicketSymbolTooLong,
+        constraint = EventTicket::is_valid_symbol(&ticket_symbol) @ ErrorCode::InvalidTicketSymbol,
+        constraint = ticket_uri.len() <= MAX_URI_LENGTH @ ErrorCode::TicketUriTooLong,
+        constraint = EventTicket::is_valid_uri(&ticket_uri) @ ErrorCode::InvalidTicketUri
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = event,
+        mint::freeze_authority = event,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    /// CHECK: this will be verified by token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            ticket_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub ticket_metadata: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    poap_name: String,
+    poap_symbol: String,
+    poap_uri: String,
+)]
+pub struct CreateProofOfAttendance<'info> {
+    /// CHECK: this is verified through an address constraint
+    #[account(address = mpl_token_metadata::ID, executable)]
+    pub metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::SeatMapsCantHaveProofOfAttendance,
+        constraint = event_ticket.sessions == 0 @ ErrorCode::PassesCantHaveProofOfAttendance
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = event,
+        seeds = [
+            b"attendance_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump,
+        constraint = poap_name.len() <= MAX_NAME_LENGTH @ ErrorCode::PoapNameTooLong,
+        constraint = EventTicket::is_valid_name(&poap_name) @ ErrorCode::InvalidPoapName,
+        constraint = poap_symbol.len() <= MAX_SYMBOL_LENGTH @ ErrorCode::PoapSymbolTooLong,
+        constraint = EventTicket::is_valid_symbol(&poap_symbol) @ ErrorCode::InvalidPoapSymbol,
+        constraint = poap_uri.len() <= MAX_URI_LENGTH @ ErrorCode::PoapUriTooLong,
+        constraint = EventTicket::is_valid_uri(&poap_uri) @ ErrorCode::InvalidPoapUri
+    )]
+    pub attendance_mint: Account<'info, Mint>,
+    /// CHECK: this will be verified by token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            attendance_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub attendance_metadata: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTicketAuction<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanManageAuctions,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.issued() == 0 @ ErrorCode::AuctionCanOnlyBeSetBeforeSales,
+        constraint = event_ticket.pricing_curve.is_none() @ ErrorCode::TicketHasPricingCurve,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+pub struct SetTicketPricingCurve<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetPricingCurves,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRaffle<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanManageRaffles,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.issued() == 0 @ ErrorCode::RaffleCanOnlyBeCreatedBeforeSales,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = event_ticket.pricing_curve.is_none() @ ErrorCode::TicketHasPricingCurve,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        space = Raffle::SIZE,
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(address = event.accepted_mint)]
+    pub accepted_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::authority = event,
+        token::mint = accepted_mint,
+        seeds = [
+            b"raffle_vault".as_ref(),
+            raffle.key().as_ref(),
+        ],
+        bump
+    )]
+    pub raffle_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CreateWaitlist<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreateWaitlists,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        space = Waitlist::SIZE,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(address = event.accepted_mint)]
+    pub accepted_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::authority = event,
+        token::mint = accepted_mint,
+        seeds = [
+            b"waitlist_vault".as_ref(),
+            waitlist.key().as_ref(),
+        ],
+        bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(sections: u16, rows: u16, seats_per_row: u16)]
+pub struct CreateSeatMap<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreateSeatMaps,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.issued() == 0 @ ErrorCode::SeatMapCanOnlyBeCreatedBeforeSales,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle,
+        constraint = !event_ticket.has_poap @ ErrorCode::SeatMapsCantHaveProofOfAttendance
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        space = SeatMap::space(sections, rows, seats_per_row)?,
+        seeds = [
+            b"seat_map".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump
+    )]
+    pub seat_map: Account<'info, SeatMap>,
+}
+
+#[derive(Accounts)]
+pub struct SetTicketSessions<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetSessions,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.issued() == 0 @ ErrorCode::SessionsCanOnlyBeSetBeforeSales,
+        constraint = !event_ticket.has_poap @ ErrorCode::PassesCantHaveProofOfAttendance
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+pub struct SetTicketReentry<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetReentry,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.used == 0 @ ErrorCode::ReentryCanOnlyBeSetBeforeCheckIns
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+pub struct SetTicketWalletLimit<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetWalletLimit,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePromo<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreatePromos,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
+        init,
+        space = Promo::SIZE,
+        payer = authority,
+        seeds = [
+            b"promo".as_ref(),
+            event.key().as_ref(),
+            code_hash.as_ref(),
+        ],
+        bump
+    )]
+    pub promo: Account<'info, Promo>,
+}
+
+#[derive(Accounts)]
+pub struct DeletePromo<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanDeletePromos,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"promo".as_ref(),
+            event.key().as_ref(),
+            promo.code_hash.as_ref(),
+        ],
+        bump = promo.bump
+    )]
+    pub promo: Account<'info, Promo>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAffiliate<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreateAffiliates,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This account is used only as a base for derivation
+    pub affiliate_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        space = Affiliate::SIZE,
+        payer = authority,
+        seeds = [
+            b"affiliate".as_ref(),
+            event.key().as_ref(),
+            affiliate_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub affiliate: Account<'info, Affiliate>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteAffiliate<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanDeleteAffiliates,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This account is used only as a base for derivation
+    pub affiliate_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"affiliate".as_ref(),
+            event.key().as_ref(),
+            affiliate_base.key().as_ref(),
+        ],
+        bump = affiliate.bump
+    )]
+    pub affiliate: Account<'info, Affiliate>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_quantity: u32)]
+pub struct BuyTickets<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: This only receives the tickets, which can be bought on their behalf.
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.available() >= ticket_quantity @ ErrorCode::NotEnoughTicketsAvailable,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = recipient
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_quantity: u32)]
+pub struct BuyAuctionTickets<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.available() >= ticket_quantity @ ErrorCode::NotEnoughTicketsAvailable,
+        constraint = event_ticket.auction.is_some() @ ErrorCode::TicketIsNotSoldThroughAuction
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = authority
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = AuctionReceipt::SIZE,
+        seeds = [
+            b"auction_receipt".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub auction_receipt: Account<'info, AuctionReceipt>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.auction.is_some() @ ErrorCode::TicketIsNotSoldThroughAuction
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuctionRebate<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.auction.is_some() @ ErrorCode::TicketIsNotSoldThroughAuction
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"auction_receipt".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = auction_receipt.bump
+    )]
+    pub auction_receipt: Account<'info, AuctionReceipt>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct EnterRaffle<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = raffle.bump,
+        constraint = !raffle.drawn @ ErrorCode::RaffleIsAlreadyDrawn
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        init,
+        payer = fee_payer,
+        space = RaffleEntry::SIZE,
+        seeds = [
+            b"raffle_entry".as_ref(),
+            raffle.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub raffle_entry: Account<'info, RaffleEntry>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle_vault".as_ref(),
+            raffle.key().as_ref(),
+        ],
+        bump = raffle.raffle_vault_bump
+    )]
+    pub raffle_vault: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct SealRaffle<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanManageRaffles,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = raffle.bump,
+        constraint = !raffle.drawn @ ErrorCode::RaffleIsAlreadyDrawn,
+        constraint = raffle.seal_slot == 0 @ ErrorCode::RaffleIsAlreadySealed
+    )]
+    pub raffle: Account<'info, Raffle>,
+}
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanManageRaffles,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = raffle.bump,
+        constraint = !raffle.drawn @ ErrorCode::RaffleIsAlreadyDrawn,
+        constraint = raffle.seal_slot > 0 @ ErrorCode::RaffleIsNotSealed
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle_vault".as_ref(),
+            raffle.key().as_ref(),
+        ],
+        bump = raffle.raffle_vault_bump
+    )]
+    pub raffle_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: This is the SlotHashes sysvar, which is read by hand because
+    /// it's too large to deserialize.
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRaffleEntry<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = raffle.bump,
+        constraint = raffle.drawn @ ErrorCode::RaffleIsNotDrawn
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"raffle_entry".as_ref(),
+            raffle.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = raffle_entry.bump
+    )]
+    pub raffle_entry: Account<'info, RaffleEntry>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = authority
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle_vault".as_ref(),
+            raffle.key().as_ref(),
+        ],
+        bump = raffle.raffle_vault_bump
+    )]
+    pub raffle_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = wallet_purchase.bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct RefundRaffleEntry<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: This only receives the refund and the entry's rent, anyone can
+    /// trigger it once the draw deadline has passed.
+    #[account(mut)]
+    pub entrant: UncheckedAccount<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    // pausing the event doesn't hold up refunds
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        seeds = [
+            b"raffle".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = raffle.bump,
+        constraint = !raffle.drawn @ ErrorCode::RaffleIsAlreadyDrawn
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        close = entrant,
+        seeds = [
+            b"raffle_entry".as_ref(),
+            raffle.key().as_ref(),
+            entrant.key().as_ref(),
+        ],
+        bump = raffle_entry.bump
+    )]
+    pub raffle_entry: Account<'info, RaffleEntry>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint,
+        constraint = buyer_vault.owner == entrant.key() @ ErrorCode::InvalidRefundVault
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"raffle_vault".as_ref(),
+            raffle.key().as_ref(),
+        ],
+        bump = raffle.raffle_vault_bump
+    )]
+    pub raffle_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            entrant.key().as_ref(),
+        ],
+        bump = wallet_purchase.bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.available() == 0 @ ErrorCode::TicketsAreStillAvailable
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        init,
+        payer = fee_payer,
+        space = WaitlistEntry::SIZE,
+        seeds = [
+            b"waitlist_entry".as_ref(),
+            waitlist.key().as_ref(),
+            waitlist.tail.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist_vault".as_ref(),
+            waitlist.key().as_ref(),
+        ],
+        bump = waitlist.waitlist_vault_bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = authority
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+#[instruction(position: u64)]
+pub struct LeaveWaitlist<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist_entry".as_ref(),
+            waitlist.key().as_ref(),
+            position.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+        constraint = waitlist_entry.owner == authority.key() @ ErrorCode::OnlyWaitlistEntryOwnerCanLeave,
+        constraint = waitlist_entry.amount > 0 @ ErrorCode::WaitlistEntryHasLeft
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist_vault".as_ref(),
+            waitlist.key().as_ref(),
+        ],
+        bump = waitlist.waitlist_vault_bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = wallet_purchase.bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct SkipWaitlistEntry<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"waitlist_entry".as_ref(),
+            waitlist.key().as_ref(),
+            waitlist.head.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+        constraint = waitlist_entry.amount == 0 @ ErrorCode::WaitlistEntryIsActive
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    /// CHECK: This is verified through an address constraint
+    #[account(mut, address = waitlist_entry.owner)]
+    pub owner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTicket<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"waitlist_entry".as_ref(),
+            waitlist.key().as_ref(),
+            waitlist.head.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+        constraint = waitlist_entry.amount > 0 @ ErrorCode::WaitlistEntryHasLeft
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    /// CHECK: This is verified through an address constraint
+    #[account(mut, address = waitlist_entry.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == authority.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = waitlist_entry.ticket_vault
+    )]
+    pub waitlist_ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist_vault".as_ref(),
+            waitlist.key().as_ref(),
+        ],
+        bump = waitlist.waitlist_vault_bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    // Tickets are fungible, so a wallet can only release while it holds more
+    // than the comps it was given.
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = wallet_purchase.bump,
+        constraint = wallet_purchase.quantity > 0 @ ErrorCode::TicketWasNotPurchased,
+        constraint = ticket_vault.amount > wallet_purchase.comps.into() @ ErrorCode::TicketWasNotPurchased
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct AllocateWaitlistEntry<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.quantity > event_ticket.sold.saturating_add(event_ticket.comps).saturating_add(event_ticket.held) @ ErrorCode::NotEnoughTicketsAvailable
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = waitlist.bump
+    )]
+    pub waitlist: Account<'info, Waitlist>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"waitlist_entry".as_ref(),
+            waitlist.key().as_ref(),
+            waitlist.head.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+        constraint = waitlist_entry.amount > 0 @ ErrorCode::WaitlistEntryHasLeft
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    /// CHECK: This is verified through an address constraint
+    #[account(mut, address = waitlist_entry.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        address = waitlist_entry.ticket_vault
+    )]
+    pub waitlist_ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"waitlist_vault".as_ref(),
+            waitlist.key().as_ref(),
+        ],
+        bump = waitlist.waitlist_vault_bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(seat_ids: Vec<u32>)]
+pub struct BuySeats<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = authority
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"seat_map".as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = seat_map.bump
+    )]
+    pub seat_map: Account<'info, SeatMap>,
+    /// CHECK: This is used only for generating the PDA.
+    pub seat_reservation_base: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = fee_payer,
+        space = SeatReservation::SIZE,
+        seeds = [
+            b"seat_reservation".as_ref(),
+            event_ticket.key().as_ref(),
+            seat_reservation_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub seat_reservation: Account<'info, SeatReservation>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_quantity: u32)]
+pub struct ReserveTickets<'info> {
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.available() >= ticket_quantity @ ErrorCode::NotEnoughTicketsAvailable,
+        constraint = event_ticket.auction.is_none() @ ErrorCode::TicketIsSoldThroughAuction,
+        constraint = !event_ticket.has_raffle @ ErrorCode::TicketIsSoldThroughRaffle,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        init,
+        payer = authority,
+        space = TicketHold::SIZE,
+        seeds = [
+            b"ticket_hold".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub ticket_hold: Account<'info, TicketHold>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteReservation<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        constraint = buyer_vault.mint == event.accepted_mint
+    )]
+    pub buyer_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_vault.mint == event.accepted_mint,
+        constraint = fee_vault.owner == config.fee_treasury @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = authority
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"ticket_hold".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = ticket_hold.bump
+    )]
+    pub ticket_hold: Account<'info, TicketHold>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseExpiredHold<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"ticket_hold".as_ref(),
+            event_ticket.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = ticket_hold.bump
+    )]
+    pub ticket_hold: Account<'info, TicketHold>,
+    /// CHECK: This is verified through an address constraint
+    #[account(mut, address = ticket_hold.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = wallet_purchase.bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_quantity: u32)]
+pub struct IssueCompTickets<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This only receives the tickets.
+    pub recipient: UncheckedAccount<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
@@ -293,16 +4690,96 @@ pub struct CreateCollaborator<'info> {
             event_base.key().as_ref(),
         ],
         bump = event.bump,
-        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanCreateCollaborators
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
-    /// CHECK: This account is used only as a base for derivation
-    pub collaborator_base: UncheckedAccount<'info>,
-    /// CHECK: This account is created in this instruction
+    /// CHECK: This account only has to exist when authority is not the event authority
     #[account(
-        init,
-        space = Collaborator::SIZE,
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+        constraint = event.authority == authority.key() || collaborator.owner == &ID @ ErrorCode::OnlyEventAuthorityOrCollaboratorsCanIssueCompTickets
+    )]
+    pub collaborator: UncheckedAccount<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.available() >= ticket_quantity @ ErrorCode::NotEnoughTicketsAvailable,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
         payer = authority,
+        space = WalletPurchase::SIZE,
+        seeds = [
+            b"wallet_purchase".as_ref(),
+            event_ticket.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump
+    )]
+    pub wallet_purchase: Box<Account<'info, WalletPurchase>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ticket_quantity: u32)]
+pub struct CheckIn<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub collaborator_base: Signer<'info>,
+    pub attendee: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
         seeds = [
             b"collaborator".as_ref(),
             event.key().as_ref(),
@@ -311,29 +4788,161 @@ pub struct CreateCollaborator<'info> {
         bump
     )]
     pub collaborator: Account<'info, Collaborator>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.outstanding() >= ticket_quantity @ ErrorCode::NotEnoughTicketsToCheckIn,
+        constraint = event_ticket.sessions == 0 @ ErrorCode::TicketIsAPass,
+        constraint = !event_ticket.allows_reentry @ ErrorCode::TicketAllowsReentry,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
 }
 
 #[derive(Accounts)]
-pub struct DeleteCollaborator<'info> {
+#[instruction(ticket_quantity: u32)]
+pub struct CheckInWithAttendance<'info> {
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub fee_payer: Signer<'info>,
+    pub collaborator_base: Signer<'info>,
+    pub attendee: Signer<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
+        mut,
         seeds = [
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
         bump = event.bump,
-        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanDeleteCollaborators
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
-    /// CHECK: This account is used only as a base for derivation
-    pub collaborator_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
         mut,
-        close = authority,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.outstanding() >= ticket_quantity @ ErrorCode::NotEnoughTicketsToCheckIn,
+        constraint = event_ticket.sessions == 0 @ ErrorCode::TicketIsAPass,
+        constraint = !event_ticket.allows_reentry @ ErrorCode::TicketAllowsReentry,
+        constraint = !event_ticket.has_seat_map @ ErrorCode::TicketHasSeatMap
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"attendance_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.attendance_mint_bump
+    )]
+    pub attendance_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = attendance_mint,
+        associated_token::authority = attendee
+    )]
+    pub attendance_vault: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(seat_id: u32)]
+pub struct CheckInSeat<'info> {
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    pub collaborator_base: Signer<'info>,
+    pub attendee: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
         seeds = [
             b"collaborator".as_ref(),
             event.key().as_ref(),
@@ -342,101 +4951,170 @@ pub struct DeleteCollaborator<'info> {
         bump
     )]
     pub collaborator: Account<'info, Collaborator>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_ticket_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_ticket".as_ref(),
+            event.key().as_ref(),
+            event_ticket_base.key().as_ref(),
+        ],
+        bump = event_ticket.bump,
+        constraint = event_ticket.sessions == 0 @ ErrorCode::TicketIsAPass,
+        constraint = !event_ticket.allows_reentry @ ErrorCode::TicketAllowsReentry
+    )]
+    pub event_ticket: Account<'info, EventTicket>,
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_mint".as_ref(),
+            event.key().as_ref(),
+            event_ticket.key().as_ref(),
+        ],
+        bump = event_ticket.ticket_mint_bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: This is used only for generating the PDA.
+    pub seat_reservation_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"seat_reservation".as_ref(),
+            event_ticket.key().as_ref(),
+            seat_reservation_base.key().as_ref(),
+        ],
+        bump = seat_reservation.bump,
+        constraint = seat_reservation.owner == attendee.key() @ ErrorCode::OnlySeatOwnerCanCheckIn
+    )]
+    pub seat_reservation: Account<'info, SeatReservation>,
 }
 
 #[derive(Accounts)]
-#[instruction(
-    ticket_name: String,
-    ticket_symbol: String,
-    ticket_uri: String,
-    ticket_price: u32,
-    ticket_quantity: u32,
-)]
-pub struct CreateEventTicket<'info> {
-    /// CHECK: this is verified through an address constraint
-    #[account(address = mpl_token_metadata::ID, executable)]
-    pub metadata_program: UncheckedAccount<'info>,
+#[instruction(session: u8, ticket_quantity: u32)]
+pub struct CheckInPass<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub fee_payer: Signer<'info>,
+    pub collaborator_base: Signer<'info>,
+    pub attendee: Signer<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
+        mut,
         seeds = [
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
-        bump = event.bump
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
+    #[account(
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
     /// CHECK: This is used only for generating the PDA.
     pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
-        init,
-        payer = authority,
-        space = EventTicket::SIZE,
+        mut,
         seeds = [
             b"event_ticket".as_ref(),
             event.key().as_ref(),
             event_ticket_base.key().as_ref(),
         ],
-        bump
+        bump = event_ticket.bump,
+        constraint = event_ticket.sessions > 0 @ ErrorCode::TicketIsNotAPass
     )]
     pub event_ticket: Account<'info, EventTicket>,
     #[account(
-        init,
-        payer = authority,
-        mint::decimals = 0,
-        mint::authority = event,
+        mut,
         seeds = [
             b"ticket_mint".as_ref(),
             event.key().as_ref(),
             event_ticket.key().as_ref(),
         ],
-        bump
+        bump = event_ticket.ticket_mint_bump
     )]
     pub ticket_mint: Account<'info, Mint>,
-    /// CHECK: this will be verified by token metadata program
     #[account(
         mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = PassUsage::space(event_ticket.sessions),
         seeds = [
-            b"metadata".as_ref(),
-            metadata_program.key().as_ref(),
-            ticket_mint.key().as_ref(),
+            b"pass_usage".as_ref(),
+            event_ticket.key().as_ref(),
+            attendee.key().as_ref(),
         ],
-        bump,
-        seeds::program = metadata_program.key()
+        bump
     )]
-    pub ticket_metadata: UncheckedAccount<'info>,
+    pub pass_usage: Account<'info, PassUsage>,
 }
 
 #[derive(Accounts)]
-#[instruction(
-    poap_name: String,
-    poap_symbol: String,
-    poap_uri: String,
-)]
-pub struct CreateProofOfAttendance<'info> {
-    /// CHECK: this is verified through an address constraint
-    #[account(address = mpl_token_metadata::ID, executable)]
-    pub metadata_program: UncheckedAccount<'info>,
+#[instruction(ticket_quantity: u32)]
+pub struct CheckInWithReentry<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// Pays for fees and rent, which lets a relayer sponsor the transaction.
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub fee_payer: Signer<'info>,
+    pub collaborator_base: Signer<'info>,
+    pub attendee: Signer<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
+        mut,
         seeds = [
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
-        bump = event.bump
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
+    #[account(
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
     /// CHECK: This is used only for generating the PDA.
     pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
@@ -446,45 +5124,51 @@ pub struct CreateProofOfAttendance<'info> {
             event.key().as_ref(),
             event_ticket_base.key().as_ref(),
         ],
-        bump = event_ticket.bump
+        bump = event_ticket.bump,
+        constraint = event_ticket.allows_reentry @ ErrorCode::TicketDoesNotAllowReentry
     )]
     pub event_ticket: Account<'info, EventTicket>,
     #[account(
-        init,
-        payer = authority,
-        mint::decimals = 0,
-        mint::authority = event,
+        mut,
         seeds = [
-            b"attendance_mint".as_ref(),
+            b"ticket_mint".as_ref(),
             event.key().as_ref(),
             event_ticket.key().as_ref(),
         ],
-        bump
+        bump = event_ticket.ticket_mint_bump
     )]
-    pub attendance_mint: Account<'info, Mint>,
-    /// CHECK: this will be verified by token metadata program
+    pub ticket_mint: Account<'info, Mint>,
     #[account(
         mut,
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
+    )]
+    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = fee_payer,
+        space = Entry::SIZE,
         seeds = [
-            b"metadata".as_ref(),
-            metadata_program.key().as_ref(),
-            attendance_mint.key().as_ref(),
+            b"entry".as_ref(),
+            event_ticket.key().as_ref(),
+            attendee.key().as_ref(),
         ],
-        bump,
-        seeds::program = metadata_program.key()
+        bump
     )]
-    pub attendance_metadata: UncheckedAccount<'info>,
+    pub entry: Account<'info, Entry>,
 }
 
 #[derive(Accounts)]
-#[instruction(ticket_quantity: u32)]
-pub struct BuyTickets<'info> {
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
+pub struct CheckOut<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub collaborator_base: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub attendee: UncheckedAccount<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
@@ -492,63 +5176,54 @@ pub struct BuyTickets<'info> {
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
-        bump = event.bump
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
+    #[account(
+        seeds = [
+            b"collaborator".as_ref(),
+            event.key().as_ref(),
+            collaborator_base.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collaborator: Account<'info, Collaborator>,
     /// CHECK: This is used only for generating the PDA.
     pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
-        mut,
         seeds = [
             b"event_ticket".as_ref(),
             event.key().as_ref(),
             event_ticket_base.key().as_ref(),
         ],
         bump = event_ticket.bump,
-        constraint = event_ticket.quantity >= event_ticket.sold + ticket_quantity @ ErrorCode::NotEnoughTicketsAvailable
+        constraint = event_ticket.allows_reentry @ ErrorCode::TicketDoesNotAllowReentry
     )]
     pub event_ticket: Account<'info, EventTicket>,
-    #[account(
-        mut,
-        constraint = buyer_vault.mint == event.accepted_mint
-    )]
-    pub buyer_vault: Account<'info, TokenAccount>,
-    #[account(
-        mut,
-        seeds = [
-            b"event_vault".as_ref(),
-            event.key().as_ref(),
-        ],
-        bump = event.event_vault_bump
-    )]
-    pub event_vault: Account<'info, TokenAccount>,
     #[account(
         mut,
         seeds = [
-            b"ticket_mint".as_ref(),
-            event.key().as_ref(),
+            b"entry".as_ref(),
             event_ticket.key().as_ref(),
+            attendee.key().as_ref(),
         ],
-        bump = event_ticket.ticket_mint_bump
-    )]
-    pub ticket_mint: Account<'info, Mint>,
-    #[account(
-        mut,
-        constraint = ticket_vault.mint == ticket_mint.key()
+        bump = entry.bump
     )]
-    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    pub entry: Account<'info, Entry>,
 }
 
 #[derive(Accounts)]
-#[instruction(ticket_quantity: u32)]
-pub struct CheckIn<'info> {
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    #[account(mut)]
+pub struct ReEnter<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
     pub collaborator_base: Signer<'info>,
-    pub attendee: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub attendee: UncheckedAccount<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
     #[account(
@@ -556,7 +5231,8 @@ pub struct CheckIn<'info> {
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
-        bump = event.bump
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
     #[account(
@@ -571,42 +5247,38 @@ pub struct CheckIn<'info> {
     /// CHECK: This is used only for generating the PDA.
     pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
-        mut,
         seeds = [
             b"event_ticket".as_ref(),
             event.key().as_ref(),
             event_ticket_base.key().as_ref(),
         ],
         bump = event_ticket.bump,
-        constraint = event_ticket.sold - event_ticket.used >= ticket_quantity @ ErrorCode::NotEnoughTicketsToCheckIn
+        constraint = event_ticket.allows_reentry @ ErrorCode::TicketDoesNotAllowReentry
     )]
     pub event_ticket: Account<'info, EventTicket>,
     #[account(
         mut,
         seeds = [
-            b"ticket_mint".as_ref(),
-            event.key().as_ref(),
+            b"entry".as_ref(),
             event_ticket.key().as_ref(),
+            attendee.key().as_ref(),
         ],
-        bump = event_ticket.ticket_mint_bump
-    )]
-    pub ticket_mint: Account<'info, Mint>,
-    #[account(
-        mut,
-        constraint = ticket_vault.mint == ticket_mint.key()
+        bump = entry.bump
     )]
-    pub ticket_vault: Box<Account<'info, TokenAccount>>,
+    pub entry: Account<'info, Entry>,
 }
 
 #[derive(Accounts)]
-#[instruction(ticket_quantity: u32)]
-pub struct CheckInWithAttendance<'info> {
-    pub system_program: Program<'info, System>,
+pub struct FinalizeEntry<'info> {
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
-    #[account(mut)]
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
     pub collaborator_base: Signer<'info>,
+    #[account(mut)]
     pub attendee: Signer<'info>,
     /// CHECK: This is used only for generating the PDA.
     pub event_base: UncheckedAccount<'info>,
@@ -615,7 +5287,8 @@ pub struct CheckInWithAttendance<'info> {
             b"event".as_ref(),
             event_base.key().as_ref(),
         ],
-        bump = event.bump
+        bump = event.bump,
+        constraint = !event.paused @ ErrorCode::EventPaused
     )]
     pub event: Account<'info, Event>,
     #[account(
@@ -630,14 +5303,13 @@ pub struct CheckInWithAttendance<'info> {
     /// CHECK: This is used only for generating the PDA.
     pub event_ticket_base: UncheckedAccount<'info>,
     #[account(
-        mut,
         seeds = [
             b"event_ticket".as_ref(),
             event.key().as_ref(),
             event_ticket_base.key().as_ref(),
         ],
         bump = event_ticket.bump,
-        constraint = event_ticket.sold - event_ticket.used >= ticket_quantity @ ErrorCode::NotEnoughTicketsToCheckIn
+        constraint = event_ticket.allows_reentry @ ErrorCode::TicketDoesNotAllowReentry
     )]
     pub event_ticket: Account<'info, EventTicket>,
     #[account(
@@ -652,37 +5324,385 @@ pub struct CheckInWithAttendance<'info> {
     pub ticket_mint: Account<'info, Mint>,
     #[account(
         mut,
-        constraint = ticket_vault.mint == ticket_mint.key()
+        constraint = ticket_vault.mint == ticket_mint.key(),
+        constraint = ticket_vault.owner == attendee.key() @ ErrorCode::InvalidTicketVaultOwner
     )]
     pub ticket_vault: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
+        close = attendee,
         seeds = [
-            b"attendance_mint".as_ref(),
-            event.key().as_ref(),
+            b"entry".as_ref(),
             event_ticket.key().as_ref(),
+            attendee.key().as_ref(),
         ],
-        bump = event_ticket.attendance_mint_bump
+        bump = entry.bump
     )]
-    pub attendance_mint: Account<'info, Mint>,
+    pub entry: Account<'info, Entry>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct Withdraw<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanWithdraw,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
     #[account(
         mut,
-        constraint = attendance_vault.mint == attendance_mint.key()
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump,
+        constraint = event.withdrawable(event_vault.amount) >= amount @ ErrorCode::InsufficientEventFunds
     )]
-    pub attendance_vault: Box<Account<'info, TokenAccount>>,
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination_vault.mint == event.accepted_mint
+    )]
+    pub destination_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventRelayer<'info> {
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.authority == authority.key() @ ErrorCode::OnlyEventAuthorityCanSetRelayer,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRelayerReimbursement<'info> {
+    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub relayer: Signer<'info>,
+    /// CHECK: This is used only for generating the PDA.
+    pub event_base: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"event".as_ref(),
+            event_base.key().as_ref(),
+        ],
+        bump = event.bump,
+        constraint = event.relayer == Some(relayer.key()) @ ErrorCode::OnlyRelayerCanClaimReimbursements,
+        constraint = !event.paused @ ErrorCode::EventPaused
+    )]
+    pub event: Account<'info, Event>,
+    #[account(
+        mut,
+        seeds = [
+            b"event_vault".as_ref(),
+            event.key().as_ref(),
+        ],
+        bump = event.event_vault_bump,
+        constraint = event_vault.amount >= event.relayer_owed @ ErrorCode::InsufficientEventFunds
+    )]
+    pub event_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = relayer_vault.mint == event.accepted_mint
+    )]
+    pub relayer_vault: Account<'info, TokenAccount>,
+}
+
+#[account]
+pub struct Event {
+    pub event_title: String, // max 128 bytes, sized per event
+    pub accepted_mint: Pubkey,
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub event_vault_bump: u8,
+    pub organizer: Option<Pubkey>,
+    pub series: Option<Pubkey>,
+    pub venue: Option<Pubkey>,
+    pub total_capacity: u32, // 0 when no venue is attached
+    pub total_allocated: u32,
+    pub total_sold: u32,
+    pub paused: bool,
+    pub start_time: i64, // 0 when unscheduled
+    pub end_time: i64,
+    pub timezone: String,     // max 32, IANA name such as "Europe/Brussels"
+    pub location: String,     // max 64, street address or geohash
+    pub tags: Vec<String>,    // max 5 tags of 16
+    pub metadata_uri: String, // max 200, see README for the JSON schema
+    pub relayer: Option<Pubkey>,
+    pub relayer_fee: u64, // reimbursed per sponsored purchase
+    pub relayer_owed: u64,
+    pub rebates_owed: u64, // auction rebates reserved until they're claimed
+}
+
+impl Event {
+    pub const MAX_TITLE_LENGTH: usize = 128;
+    pub const MAX_TIMEZONE_LENGTH: usize = 32;
+    pub const MAX_LOCATION_LENGTH: usize = 64;
+    pub const MAX_TAGS: usize = 5;
+    pub const MAX_TAG_LENGTH: usize = 16;
+    pub const MAX_METADATA_URI_LENGTH: usize = 200;
+    // Everything but the title bytes, which are only paid for as needed. The
+    // metadata fields are reserved up front so updates never need a realloc.
+    pub const BASE_SIZE: usize = 8
+        + 4
+        + 32
+        + 32
+        + 1
+        + 1
+        + (1 + 32)
+        + (1 + 32)
+        + (1 + 32)
+        + 4
+        + 4
+        + 4
+        + 1
+        + 8
+        + 8
+        + (4 + Event::MAX_TIMEZONE_LENGTH)
+        + (4 + Event::MAX_LOCATION_LENGTH)
+        + (4 + Event::MAX_TAGS * (4 + Event::MAX_TAG_LENGTH))
+        + (4 + Event::MAX_METADATA_URI_LENGTH)
+        + (1 + 32)
+        + 8
+        + 8
+        + 8;
+
+    pub fn size(event_title: &str) -> usize {
+        Event::BASE_SIZE + event_title.len()
+    }
+
+    pub fn init(
+        &mut self,
+        event_title: String,
+        accepted_mint: Pubkey,
+        authority: Pubkey,
+        organizer: Option<Pubkey>,
+        bump: u8,
+        event_vault_bump: u8,
+    ) {
+        self.event_title = event_title;
+        self.accepted_mint = accepted_mint;
+        self.authority = authority;
+        self.bump = bump;
+        self.event_vault_bump = event_vault_bump;
+        self.organizer = organizer;
+        self.series = None;
+        self.venue = None;
+        self.total_capacity = 0;
+        self.total_allocated = 0;
+        self.total_sold = 0;
+        self.paused = false;
+        self.start_time = 0;
+        self.end_time = 0;
+        self.timezone = String::new();
+        self.location = String::new();
+        self.tags = vec![];
+        self.metadata_uri = String::new();
+        self.relayer = None;
+        self.relayer_fee = 0;
+        self.relayer_owed = 0;
+        self.rebates_owed = 0;
+    }
+
+    pub fn is_valid_title(event_title: &str) -> bool {
+        !event_title.trim().is_empty() && !event_title.chars().any(char::is_control)
+    }
+
+    pub fn is_relayer(&self, fee_payer: &Pubkey) -> bool {
+        self.relayer == Some(*fee_payer)
+    }
+
+    // Sponsored purchases are reimbursed to the relayer later, never with more
+    // than the event made on them, so a relayer can't drain the vault by
+    // sponsoring its own purchases.
+    pub fn accrue_relayer_fee(&mut self, fee_payer: &Pubkey, revenue: u64) -> Result<()> {
+        if self.is_relayer(fee_payer) {
+            self.relayer_owed = self
+                .relayer_owed
+                .checked_add(self.relayer_fee.min(revenue))
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    // Funds owed to the relayer and to auction bidders stay in the vault until
+    // they are claimed. Raffle deposits never reach it before the draw, and
+    // losing entries are refunded from the raffle vault.
+    pub fn withdrawable(&self, vault_amount: u64) -> u64 {
+        vault_amount
+            .saturating_sub(self.relayer_owed)
+            .saturating_sub(self.rebates_owed)
+    }
+
+    pub fn is_valid_schedule(start_time: i64, end_time: i64) -> bool {
+        (start_time == 0 && end_time == 0) || (0 < start_time && start_time <= end_time)
+    }
+
+    pub fn is_valid_timezone(timezone: &str) -> bool {
+        timezone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'))
+    }
+
+    pub fn is_valid_location(location: &str) -> bool {
+        !location.chars().any(char::is_control)
+    }
+
+    pub fn is_valid_tag(tag: &str) -> bool {
+        !tag.is_empty()
+            && tag.len() <= Event::MAX_TAG_LENGTH
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    }
+
+    pub fn is_valid_metadata_uri(metadata_uri: &str) -> bool {
+        metadata_uri.chars().all(|c| c.is_ascii_graphic())
+    }
+
+    // Tiers can only be allocated what's left of the venue, which also keeps
+    // sales across tiers within its capacity.
+    pub fn can_allocate(&self, ticket_quantity: u32) -> bool {
+        self.total_capacity == 0
+            || matches!(
+                self.total_allocated.checked_add(ticket_quantity),
+                Some(total) if total <= self.total_capacity
+            )
+    }
 }
 
 #[account]
-pub struct Event {
-    pub event_title: String, // max 32
-    pub accepted_mint: Pubkey,
+pub struct Venue {
     pub authority: Pubkey,
+    pub name: String,
+    pub location_uri: String,
+    pub capacity: u32,
     pub bump: u8,
-    pub event_vault_bump: u8,
 }
 
-impl Event {
-    pub const SIZE: usize = 8 + 36 + 32 + 32 + 1 + 1;
+impl Venue {
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const MAX_LOCATION_URI_LENGTH: usize = 200;
+    pub const SIZE: usize =
+        8 + 32 + (4 + Venue::MAX_NAME_LENGTH) + (4 + Venue::MAX_LOCATION_URI_LENGTH) + 4 + 1;
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub fee_treasury: Pubkey,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const SIZE: usize = 8 + 32 + 2 + 32 + 1 + 1;
+
+    pub fn fee(&self, amount: u64) -> Result<u64> {
+        Ok(amount
+            .checked_mul(self.fee_bps.into())
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / 10_000)
+    }
+
+    // Every ticket payment goes through here: `payment` moves `amount` into the
+    // event vault, minus the platform fee, which goes to `fee_vault` instead.
+    pub fn collect_payment<'info>(
+        &self,
+        payment: CpiContext<'_, '_, '_, 'info, Transfer<'info>>,
+        fee_vault: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<u64> {
+        let fee = self.fee(amount)?;
+        let fee_payment = CpiContext::new_with_signer(
+            payment.program.clone(),
+            Transfer {
+                from: payment.accounts.from.clone(),
+                to: fee_vault,
+                authority: payment.accounts.authority.clone(),
+            },
+            payment.signer_seeds,
+        );
+
+        transfer(
+            payment,
+            amount
+                .checked_sub(fee)
+                .ok_or(ErrorCode::ArithmeticOverflow)?,
+        )?;
+        transfer(fee_payment, fee)?;
+
+        Ok(fee)
+    }
+}
+
+#[account]
+pub struct Organizer {
+    pub authority: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub verified: bool,
+    pub event_count: u32,
+    pub bump: u8,
+}
+
+impl Organizer {
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const MAX_URI_LENGTH: usize = 200;
+    pub const SIZE: usize =
+        8 + 32 + (4 + Organizer::MAX_NAME_LENGTH) + (4 + Organizer::MAX_URI_LENGTH) + 1 + 4 + 1;
+}
+
+#[account]
+pub struct Series {
+    pub organizer: Pubkey,
+    pub name: String,
+    pub event_count: u32,
+    pub bump: u8,
+}
+
+impl Series {
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const SIZE: usize = 8 + 32 + (4 + Series::MAX_NAME_LENGTH) + 4 + 1;
 }
 
 #[account]
@@ -700,7 +5720,17 @@ pub struct EventTicket {
     pub quantity: u32,
     pub sold: u32,
     pub used: u32,
+    pub comps: u32,
+    pub waitlisted: u32,
+    pub held: u32,
+    pub auction: Option<DutchAuction>,
+    pub pricing_curve: Option<PricingCurve>,
     pub has_poap: bool,
+    pub has_raffle: bool,
+    pub has_seat_map: bool,
+    pub sessions: u8,
+    pub allows_reentry: bool,
+    pub max_per_wallet: u32, // 0 when unlimited
     pub bump: u8,
     pub ticket_mint_bump: u8,
     pub ticket_metadata_bump: u8,
@@ -709,7 +5739,524 @@ pub struct EventTicket {
 }
 
 impl EventTicket {
-    pub const SIZE: usize = 8 + 4 + 4 + 4 + 4 + 1 + 1 + 1 + 1 + 1 + 1;
+    pub const SIZE: usize = 8
+        + 4
+        + 4
+        + 4
+        + 4
+        + 4
+        + 4
+        + 4
+        + (1 + DutchAuction::SIZE)
+        + (1 + PricingCurve::SIZE)
+        + 1
+        + 1
+        + 1
+        + 1
+        + 1
+        + 4
+        + 1
+        + 1
+        + 1
+        + 1
+        + 1;
+
+    pub fn available(&self) -> u32 {
+        self.quantity.saturating_sub(
+            self.sold
+                .saturating_add(self.comps)
+                .saturating_add(self.waitlisted)
+                .saturating_add(self.held),
+        )
+    }
+
+    // Tickets that were either sold or comped.
+    pub fn issued(&self) -> u32 {
+        self.sold.saturating_add(self.comps)
+    }
+
+    // Tickets that were sold or comped but not checked in yet.
+    pub fn outstanding(&self) -> u32 {
+        self.issued().saturating_sub(self.used)
+    }
+
+    // Sums the unit prices of the next `ticket_quantity` tickets in closed form,
+    // so large purchases cost the same compute as small ones.
+    pub fn total_price(&self, ticket_quantity: u32) -> Result<u32> {
+        let base = u64::from(self.price)
+            .checked_mul(u64::from(ticket_quantity))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let markup = match self.pricing_curve {
+            None => 0,
+            Some(curve) => {
+                let start = u128::from(self.sold);
+                let end = start + u128::from(ticket_quantity);
+                curve.markup_before(end) - curve.markup_before(start)
+            }
+        };
+
+        Ok(u128::from(base)
+            .checked_add(markup)
+            .and_then(|total| u32::try_from(total).ok())
+            .ok_or(ErrorCode::ArithmeticOverflow)?)
+    }
+
+    pub fn is_within_wallet_limit(&self, purchased: u32) -> bool {
+        self.max_per_wallet == 0 || purchased <= self.max_per_wallet
+    }
+
+    pub fn is_valid_name(ticket_name: &str) -> bool {
+        !ticket_name.trim().is_empty() && !ticket_name.chars().any(char::is_control)
+    }
+
+    pub fn is_valid_symbol(ticket_symbol: &str) -> bool {
+        !ticket_symbol.is_empty() && ticket_symbol.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    pub fn is_valid_uri(ticket_uri: &str) -> bool {
+        !ticket_uri.is_empty() && ticket_uri.chars().all(|c| c.is_ascii_graphic())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum PricingCurve {
+    Linear { increment: u32 },
+    Stepped { step_size: u32, increment: u32 },
+}
+
+impl PricingCurve {
+    pub const SIZE: usize = 1 + 4 + 4;
+
+    // Combined markup of the first `count` tickets of the tier.
+    pub fn markup_before(&self, count: u128) -> u128 {
+        match *self {
+            PricingCurve::Linear { increment } => {
+                u128::from(increment) * (count * count.saturating_sub(1) / 2)
+            }
+            PricingCurve::Stepped {
+                step_size,
+                increment,
+            } => {
+                let step_size = u128::from(step_size);
+                let (steps, rest) = (count / step_size, count % step_size);
+
+                u128::from(increment)
+                    * (step_size * (steps * steps.saturating_sub(1) / 2) + rest * steps)
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DutchAuction {
+    pub start_price: u32,
+    pub floor_price: u32,
+    pub start_time: i64,
+    pub decay_interval: i64,
+    pub decay_amount: u32,
+    pub with_rebates: bool,
+    pub last_price: u32,
+    pub clearing_price: Option<u32>,
+    pub sold: u32,
+}
+
+impl DutchAuction {
+    pub const SIZE: usize = 4 + 4 + 8 + 8 + 4 + 1 + 4 + 5 + 4;
+
+    pub fn price_at(&self, now: i64) -> u32 {
+        if now <= self.start_time {
+            return self.start_price;
+        }
+
+        let steps = (now.saturating_sub(self.start_time) / self.decay_interval) as u64;
+        let decay = steps.saturating_mul(self.decay_amount.into());
+
+        u64::from(self.start_price)
+            .saturating_sub(decay)
+            .max(self.floor_price.into()) as u32
+    }
+}
+
+#[account]
+pub struct AuctionReceipt {
+    pub quantity: u32,
+    pub amount_paid: u64,
+    pub bump: u8,
+}
+
+impl AuctionReceipt {
+    pub const SIZE: usize = 8 + 4 + 8 + 1;
+}
+
+#[account]
+pub struct WalletPurchase {
+    pub quantity: u32,
+    pub held: u32, // reserved through a ticket hold but not paid for yet
+    pub amount_paid: u64,
+    pub comps: u32, // comped tickets received, which aren't refundable
+    pub bump: u8,
+}
+
+impl WalletPurchase {
+    pub const SIZE: usize = 8 + 4 + 4 + 8 + 4 + 1;
+
+    // tickets a wallet is still waiting on through a raffle or the waitlist
+    // count against its limit until they're released
+    pub fn record(
+        &mut self,
+        event_ticket: &EventTicket,
+        ticket_quantity: u32,
+        amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let purchased = self
+            .quantity
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        self.check_limit(event_ticket, purchased.checked_add(self.held))?;
+
+        self.quantity = purchased;
+        self.amount_paid = self
+            .amount_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    // held tickets count against the limit too, or a wallet could keep the
+    // whole tier on hold for the cost of rent
+    pub fn hold(
+        &mut self,
+        event_ticket: &EventTicket,
+        ticket_quantity: u32,
+        bump: u8,
+    ) -> Result<()> {
+        let held = self
+            .held
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        self.check_limit(event_ticket, held.checked_add(self.quantity))?;
+
+        self.held = held;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    pub fn release_hold(&mut self, ticket_quantity: u32) -> Result<()> {
+        self.held = self
+            .held
+            .checked_sub(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    fn check_limit(&self, event_ticket: &EventTicket, total: Option<u32>) -> Result<()> {
+        let total = total.ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if !event_ticket.is_within_wallet_limit(total) {
+            return Err(ErrorCode::WalletLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn release(&mut self, ticket_quantity: u32, amount: u64) -> Result<()> {
+        self.quantity = self
+            .quantity
+            .checked_sub(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.amount_paid = self
+            .amount_paid
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    // comps don't count against the wallet limit
+    pub fn record_comps(&mut self, ticket_quantity: u32, bump: u8) -> Result<()> {
+        self.comps = self
+            .comps
+            .checked_add(ticket_quantity)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    // tickets bought at different prices can't be told apart, so each one is
+    // worth the average
+    pub fn price_paid(&self) -> Result<u64> {
+        self.amount_paid
+            .checked_div(self.quantity.into())
+            .ok_or_else(|| ErrorCode::TicketWasNotPurchased.into())
+    }
+}
+
+#[account]
+pub struct Raffle {
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub commitment: [u8; 32],
+    pub entries: u32,
+    pub winners: u32,
+    pub winning_offset: u32,
+    pub drawn: bool,
+    pub seal_slot: u64, // 0 until the authority seals the raffle for drawing
+    pub bump: u8,
+    pub raffle_vault_bump: u8,
+}
+
+impl Raffle {
+    pub const SIZE: usize = 8 + 8 + 8 + 32 + 4 + 4 + 4 + 1 + 8 + 1 + 1;
+    // Entrants can take their deposits back if the raffle isn't drawn in time.
+    pub const DRAW_WINDOW: i64 = 3 * 24 * 60 * 60;
+
+    pub fn draw_deadline(&self) -> i64 {
+        self.registration_end.saturating_add(Raffle::DRAW_WINDOW)
+    }
+
+    // SlotHashes only keeps recent slots, newest first, so the draw has to
+    // follow the seal closely.
+    pub fn slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+        let data = slot_hashes.try_borrow_data()?;
+        let entries = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+
+        for entry in data[8..].chunks_exact(40).take(entries) {
+            let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+
+            if entry_slot == slot {
+                return Ok(entry[8..].try_into().unwrap());
+            }
+
+            if entry_slot < slot {
+                break;
+            }
+        }
+
+        Err(ErrorCode::RaffleSealIsNotAvailable.into())
+    }
+
+    // Winners are the `winners` consecutive entries starting at the random offset,
+    // wrapping around, so every entry has the same odds of winning.
+    pub fn is_winner(&self, index: u32) -> bool {
+        let offset = u64::from(index) + u64::from(self.entries) - u64::from(self.winning_offset);
+
+        offset % u64::from(self.entries) < u64::from(self.winners)
+    }
+}
+
+#[account]
+pub struct RaffleEntry {
+    pub index: u32,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl RaffleEntry {
+    pub const SIZE: usize = 8 + 4 + 8 + 1;
+}
+
+#[account]
+pub struct Waitlist {
+    pub head: u64,
+    pub tail: u64,
+    pub bump: u8,
+    pub waitlist_vault_bump: u8,
+}
+
+impl Waitlist {
+    pub const SIZE: usize = 8 + 8 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct WaitlistEntry {
+    pub owner: Pubkey,
+    pub ticket_vault: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl WaitlistEntry {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct SeatMap {
+    pub sections: u16,
+    pub rows: u16,
+    pub seats_per_row: u16,
+    pub taken: Vec<u8>,
+    pub bump: u8,
+}
+
+impl SeatMap {
+    pub fn bitmap_len(sections: u16, rows: u16, seats_per_row: u16) -> usize {
+        (usize::from(sections) * usize::from(rows) * usize::from(seats_per_row)).div_ceil(8)
+    }
+
+    // Seat maps are allocated through a CPI, which can't allocate more than
+    // MAX_PERMITTED_DATA_INCREASE bytes at once.
+    pub fn space(sections: u16, rows: u16, seats_per_row: u16) -> Result<usize> {
+        let space = 8 + 2 + 2 + 2 + 4 + SeatMap::bitmap_len(sections, rows, seats_per_row) + 1;
+
+        if space > MAX_PERMITTED_DATA_INCREASE {
+            return Err(ErrorCode::SeatMapTooLarge.into());
+        }
+
+        Ok(space)
+    }
+
+    // Seat ids are laid out section by section, then row by row.
+    // Computed in u64, since three u16 dimensions can overflow a u32.
+    pub fn seat_id(&self, section: u16, row: u16, seat: u16) -> u64 {
+        (u64::from(section) * u64::from(self.rows) + u64::from(row)) * u64::from(self.seats_per_row)
+            + u64::from(seat)
+    }
+
+    pub fn capacity(&self) -> u64 {
+        u64::from(self.sections) * u64::from(self.rows) * u64::from(self.seats_per_row)
+    }
+
+    pub fn take(&mut self, seat_id: u32) -> Result<()> {
+        if u64::from(seat_id) >= self.capacity() {
+            return Err(ErrorCode::InvalidSeat.into());
+        }
+
+        let byte = &mut self.taken[(seat_id / 8) as usize];
+        let mask = 1 << (seat_id % 8);
+
+        if *byte & mask != 0 {
+            return Err(ErrorCode::SeatIsAlreadyTaken.into());
+        }
+
+        *byte |= mask;
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct SeatReservation {
+    pub owner: Pubkey,
+    pub seats: Vec<ReservedSeat>,
+    pub bump: u8,
+}
+
+impl SeatReservation {
+    pub const MAX_SEATS: usize = 10;
+    pub const SIZE: usize = 8 + 32 + 4 + SeatReservation::MAX_SEATS * ReservedSeat::SIZE + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ReservedSeat {
+    pub seat_id: u32,
+    pub checked_in: bool,
+}
+
+impl ReservedSeat {
+    pub const SIZE: usize = 4 + 1;
+}
+
+#[account]
+pub struct TicketHold {
+    pub owner: Pubkey,
+    pub quantity: u32,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl TicketHold {
+    pub const SIZE: usize = 8 + 32 + 4 + 8 + 1;
+    pub const DURATION: i64 = 5 * 60;
+}
+
+#[account]
+pub struct PassUsage {
+    pub owner: Pubkey,
+    pub passes: u32,
+    pub sessions_used: Vec<u32>,
+    pub bump: u8,
+}
+
+impl PassUsage {
+    pub fn space(sessions: u8) -> usize {
+        8 + 32 + 4 + 4 + 4 * usize::from(sessions) + 1
+    }
+}
+
+#[account]
+pub struct Entry {
+    pub owner: Pubkey,
+    pub quantity: u32,
+    pub inside: bool,
+    pub entered_at: i64,
+    pub exited_at: i64,
+    pub bump: u8,
+}
+
+impl Entry {
+    pub const SIZE: usize = 8 + 32 + 4 + 1 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct Promo {
+    pub code_hash: [u8; 32],
+    pub discount_kind: DiscountKind,
+    pub discount_value: u32,
+    pub max_redemptions: u32,
+    pub redemptions: u32,
+    pub event_ticket: Option<Pubkey>,
+    pub expires_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl Promo {
+    pub const SIZE: usize = 8 + 32 + 1 + 4 + 4 + 4 + 33 + 9 + 1;
+
+    pub fn apply(&self, amount: u32, ticket_quantity: u32) -> u32 {
+        match self.discount_kind {
+            DiscountKind::Percentage => {
+                (u64::from(amount) * u64::from(100u32.saturating_sub(self.discount_value)) / 100)
+                    as u32
+            }
+            DiscountKind::Fixed => {
+                amount.saturating_sub(self.discount_value.saturating_mul(ticket_quantity))
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DiscountKind {
+    Percentage,
+    Fixed,
+}
+
+#[account]
+pub struct Affiliate {
+    pub authority: Pubkey,
+    pub commission_bps: u16,
+    pub tickets_sold: u32,
+    pub commission_earned: u64,
+    pub bump: u8,
+}
+
+impl Affiliate {
+    pub const SIZE: usize = 8 + 32 + 2 + 4 + 8 + 1;
+
+    pub fn commission(&self, amount: u64) -> Result<u64> {
+        Ok(amount
+            .checked_mul(self.commission_bps.into())
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / 10_000)
+    }
 }
 
 #[error_code]
@@ -724,4 +6271,254 @@ pub enum ErrorCode {
     OnlyEventAuthorityCanDeleteCollaborators,
     #[msg("Check in instruction is only available for tickets without attendance.")]
     CheckInIsOnlyAvailableForTicketsWithoutAttendance,
+    #[msg("Only event authority or collaborators can issue comp tickets.")]
+    OnlyEventAuthorityOrCollaboratorsCanIssueCompTickets,
+    #[msg("Only event authority can create promos.")]
+    OnlyEventAuthorityCanCreatePromos,
+    #[msg("Only event authority can delete promos.")]
+    OnlyEventAuthorityCanDeletePromos,
+    #[msg("Percentage discounts can't be greater than 100.")]
+    InvalidPromoDiscount,
+    #[msg("Promo is not valid for this ticket.")]
+    PromoIsNotValidForThisTicket,
+    #[msg("Promo has expired.")]
+    PromoHasExpired,
+    #[msg("Promo has no redemptions left.")]
+    PromoHasNoRedemptionsLeft,
+    #[msg("Only event authority can create affiliates.")]
+    OnlyEventAuthorityCanCreateAffiliates,
+    #[msg("Only event authority can delete affiliates.")]
+    OnlyEventAuthorityCanDeleteAffiliates,
+    #[msg("Commission rate can't be greater than 10000 basis points.")]
+    InvalidCommissionRate,
+    #[msg("Only event authority can manage auctions.")]
+    OnlyEventAuthorityCanManageAuctions,
+    #[msg("Auction floor price can't exceed start price and decay interval must be positive.")]
+    InvalidAuctionSchedule,
+    #[msg("Auction can only be set before any ticket is sold.")]
+    AuctionCanOnlyBeSetBeforeSales,
+    #[msg("Ticket is sold through an auction.")]
+    TicketIsSoldThroughAuction,
+    #[msg("Ticket is not sold through an auction.")]
+    TicketIsNotSoldThroughAuction,
+    #[msg("Auction has not started.")]
+    AuctionHasNotStarted,
+    #[msg("Auction is still running.")]
+    AuctionIsStillRunning,
+    #[msg("Auction is already settled.")]
+    AuctionIsAlreadySettled,
+    #[msg("Auction is not settled.")]
+    AuctionIsNotSettled,
+    #[msg("Auction rebates are disabled.")]
+    AuctionRebatesAreDisabled,
+    #[msg("Only event authority can set pricing curves.")]
+    OnlyEventAuthorityCanSetPricingCurves,
+    #[msg("Stepped pricing curves need a step size greater than zero.")]
+    InvalidPricingCurve,
+    #[msg("Ticket has a pricing curve.")]
+    TicketHasPricingCurve,
+    #[msg("Only event authority can manage raffles.")]
+    OnlyEventAuthorityCanManageRaffles,
+    #[msg("Raffle registration has to end after it starts.")]
+    InvalidRaffleSchedule,
+    #[msg("Raffle can only be created before any ticket is sold.")]
+    RaffleCanOnlyBeCreatedBeforeSales,
+    #[msg("Ticket is sold through a raffle.")]
+    TicketIsSoldThroughRaffle,
+    #[msg("Raffle registration is closed.")]
+    RaffleRegistrationIsClosed,
+    #[msg("Raffle registration is still open.")]
+    RaffleRegistrationIsStillOpen,
+    #[msg("Raffle secret doesn't match the commitment.")]
+    InvalidRaffleSecret,
+    #[msg("Raffle is already drawn.")]
+    RaffleIsAlreadyDrawn,
+    #[msg("Raffle is not drawn.")]
+    RaffleIsNotDrawn,
+    #[msg("Only event authority can create waitlists.")]
+    OnlyEventAuthorityCanCreateWaitlists,
+    #[msg("Tickets are still available.")]
+    TicketsAreStillAvailable,
+    #[msg("Only the waitlist entry owner can leave the waitlist.")]
+    OnlyWaitlistEntryOwnerCanLeave,
+    #[msg("Waitlist entry has left the waitlist.")]
+    WaitlistEntryHasLeft,
+    #[msg("Waitlist entry is still active.")]
+    WaitlistEntryIsActive,
+    #[msg("Only event authority can create seat maps.")]
+    OnlyEventAuthorityCanCreateSeatMaps,
+    #[msg("Seat map can only be created before any ticket is sold.")]
+    SeatMapCanOnlyBeCreatedBeforeSales,
+    #[msg("Ticket has a seat map, use buy seats instead.")]
+    TicketHasSeatMap,
+    #[msg("Seats have to be bought between 1 and 10 at a time.")]
+    InvalidSeatQuantity,
+    #[msg("Seat doesn't exist in the seat map.")]
+    InvalidSeat,
+    #[msg("Seat is already taken.")]
+    SeatIsAlreadyTaken,
+    #[msg("Only the seat owner can check-in.")]
+    OnlySeatOwnerCanCheckIn,
+    #[msg("Seat is not in this reservation.")]
+    SeatIsNotInReservation,
+    #[msg("Seat is already checked in.")]
+    SeatIsAlreadyCheckedIn,
+    #[msg("Ticket hold has expired.")]
+    TicketHoldHasExpired,
+    #[msg("Ticket hold has not expired.")]
+    TicketHoldHasNotExpired,
+    #[msg("Only event authority can set sessions.")]
+    OnlyEventAuthorityCanSetSessions,
+    #[msg("Sessions can only be set before any ticket is sold.")]
+    SessionsCanOnlyBeSetBeforeSales,
+    #[msg("Ticket is a pass, use check-in pass instead.")]
+    TicketIsAPass,
+    #[msg("Ticket is not a pass.")]
+    TicketIsNotAPass,
+    #[msg("Session doesn't exist for this pass.")]
+    InvalidSession,
+    #[msg("Only event authority can set re-entry.")]
+    OnlyEventAuthorityCanSetReentry,
+    #[msg("Re-entry can only be set before any check-in.")]
+    ReentryCanOnlyBeSetBeforeCheckIns,
+    #[msg("Ticket allows re-entry, use check-in with re-entry instead.")]
+    TicketAllowsReentry,
+    #[msg("Ticket does not allow re-entry.")]
+    TicketDoesNotAllowReentry,
+    #[msg("Attendee is not inside.")]
+    AttendeeIsNotInside,
+    #[msg("Attendee is already inside.")]
+    AttendeeIsAlreadyInside,
+    #[msg("Name is too long.")]
+    NameTooLong,
+    #[msg("URI is too long.")]
+    UriTooLong,
+    #[msg("Series belongs to another organizer.")]
+    SeriesBelongsToAnotherOrganizer,
+    #[msg("Event belongs to another organizer.")]
+    EventBelongsToAnotherOrganizer,
+    #[msg("Only event authority can add the event to a series.")]
+    OnlyEventAuthorityCanAddToSeries,
+    #[msg("Event is already in a series.")]
+    EventAlreadyInSeries,
+    #[msg("Venue capacity must be greater than zero.")]
+    InvalidVenueCapacity,
+    #[msg("Only event authority can set the venue.")]
+    OnlyEventAuthorityCanSetVenue,
+    #[msg("Venue capacity exceeded.")]
+    VenueCapacityExceeded,
+    #[msg("Fee rate can't be greater than 10000 basis points.")]
+    InvalidFeeRate,
+    #[msg("Only config admin can update the config.")]
+    OnlyConfigAdminCanUpdateConfig,
+    #[msg("Only config admin can verify organizers.")]
+    OnlyConfigAdminCanVerifyOrganizers,
+    #[msg("Fee vault must belong to the fee treasury.")]
+    InvalidFeeVault,
+    #[msg("Program is paused.")]
+    ProgramPaused,
+    #[msg("Event is paused.")]
+    EventPaused,
+    #[msg("Only event authority can pause the event.")]
+    OnlyEventAuthorityCanPauseEvent,
+    #[msg("Only event authority can withdraw.")]
+    OnlyEventAuthorityCanWithdraw,
+    #[msg("Event vault doesn't have enough funds.")]
+    InsufficientEventFunds,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+    #[msg("Quantity must be greater than zero.")]
+    InvalidQuantity,
+    #[msg("Event title is too long.")]
+    EventTitleTooLong,
+    #[msg("Event title must not be blank or contain control characters.")]
+    InvalidEventTitle,
+    #[msg("Ticket name is too long.")]
+    TicketNameTooLong,
+    #[msg("Ticket name must not be blank or contain control characters.")]
+    InvalidTicketName,
+    #[msg("Ticket symbol is too long.")]
+    TicketSymbolTooLong,
+    #[msg("Ticket symbol can only contain ASCII letters and digits.")]
+    InvalidTicketSymbol,
+    #[msg("Ticket URI is too long.")]
+    TicketUriTooLong,
+    #[msg("Ticket URI must not be empty or contain whitespace.")]
+    InvalidTicketUri,
+    #[msg("Only event authority can update the event.")]
+    OnlyEventAuthorityCanUpdateEvent,
+    #[msg("Event has to start before it ends.")]
+    InvalidEventSchedule,
+    #[msg("Timezone is too long.")]
+    TimezoneTooLong,
+    #[msg("Timezone must be an IANA name.")]
+    InvalidTimezone,
+    #[msg("Location is too long.")]
+    LocationTooLong,
+    #[msg("Location must not contain control characters.")]
+    InvalidLocation,
+    #[msg("Event has too many tags.")]
+    TooManyTags,
+    #[msg("Tags must be 1 to 16 lowercase letters, digits or dashes.")]
+    InvalidTag,
+    #[msg("Metadata URI is too long.")]
+    MetadataUriTooLong,
+    #[msg("Metadata URI must not contain whitespace.")]
+    InvalidMetadataUri,
+    #[msg("Ticket vault must belong to the ticket holder.")]
+    InvalidTicketVaultOwner,
+    #[msg("Only event authority can set the wallet limit.")]
+    OnlyEventAuthorityCanSetWalletLimit,
+    #[msg("Wallet limit exceeded.")]
+    WalletLimitExceeded,
+    #[msg("Only event authority can set the relayer.")]
+    OnlyEventAuthorityCanSetRelayer,
+    #[msg("Relayer has unclaimed reimbursements.")]
+    RelayerHasUnclaimedReimbursements,
+    #[msg("Only the relayer can claim reimbursements.")]
+    OnlyRelayerCanClaimReimbursements,
+    #[msg("Name must not be blank or contain control characters.")]
+    InvalidName,
+    #[msg("Promo code doesn't match the promo account.")]
+    InvalidPromoCode,
+    #[msg("Affiliate doesn't match the affiliate account.")]
+    InvalidAffiliate,
+    #[msg("Affiliate vault must belong to the affiliate.")]
+    InvalidAffiliateVault,
+    #[msg("Only the program upgrade authority can initialize the config.")]
+    OnlyUpgradeAuthorityCanInitializeConfig,
+    #[msg("Seat map is too large.")]
+    SeatMapTooLarge,
+    #[msg("Seat maps and proof of attendance can't be combined.")]
+    SeatMapsCantHaveProofOfAttendance,
+    #[msg("Passes and proof of attendance can't be combined.")]
+    PassesCantHaveProofOfAttendance,
+    #[msg("Raffle must be sealed before it's drawn.")]
+    RaffleIsNotSealed,
+    #[msg("Raffle is already sealed.")]
+    RaffleIsAlreadySealed,
+    #[msg("Raffle seal is too old, the raffle can only be refunded.")]
+    RaffleSealIsNotAvailable,
+    #[msg("Raffle draw deadline has passed.")]
+    RaffleDrawDeadlineHasPassed,
+    #[msg("Raffle draw deadline has not passed yet.")]
+    RaffleDrawDeadlineHasNotPassed,
+    #[msg("Refund vault must belong to the entrant.")]
+    InvalidRefundVault,
+    #[msg("Only purchased tickets can be released.")]
+    TicketWasNotPurchased,
+    #[msg("Only the venue authority can host events at the venue.")]
+    OnlyVenueAuthorityCanHostEvents,
+    #[msg("POAP name is too long.")]
+    PoapNameTooLong,
+    #[msg("POAP name must not be blank or contain control characters.")]
+    InvalidPoapName,
+    #[msg("POAP symbol is too long.")]
+    PoapSymbolTooLong,
+    #[msg("POAP symbol can only contain ASCII letters and digits.")]
+    InvalidPoapSymbol,
+    #[msg("POAP URI is too long.")]
+    PoapUriTooLong,
+    #[msg("POAP URI must not be empty or contain whitespace.")]
+    InvalidPoapUri,
 }
@@ -0,0 +1,556 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::{ErrorCode, PricingCurve};
+use disco_client::{
+    accounts::{AuctionReceipt, Event, EventTicket},
+    instructions,
+    pda::find_auction_receipt_address,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const START_PRICE: u32 = 10;
+const FLOOR_PRICE: u32 = 4;
+const DECAY_INTERVAL: i64 = 60;
+const DECAY_AMOUNT: u32 = 2;
+
+async fn set_auction(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    start_time: i64,
+    with_rebates: bool,
+) -> solana_sdk::transport::Result<()> {
+    let authority = harness.payer();
+
+    harness
+        .process(
+            &[instructions::set_ticket_auction(
+                authority,
+                *event_base,
+                *event_ticket_base,
+                START_PRICE,
+                FLOOR_PRICE,
+                start_time,
+                DECAY_INTERVAL,
+                DECAY_AMOUNT,
+                with_rebates,
+            )],
+            &[],
+        )
+        .await
+}
+
+async fn buy_auction_tickets(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    buyer: &Keypair,
+    buyer_vault: &Pubkey,
+    ticket_quantity: u32,
+) -> solana_sdk::transport::Result<()> {
+    harness
+        .process(
+            &[instructions::buy_auction_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                *buyer_vault,
+                harness.fee_vault,
+                ticket_quantity,
+            )],
+            &[buyer],
+        )
+        .await
+}
+
+#[tokio::test]
+async fn runs_dutch_auction_with_rebates() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &buyer.pubkey())
+        .await;
+    let start_time = harness.now().await + 100;
+
+    set_auction(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        start_time,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let result = buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await;
+    assert_error(result, ErrorCode::AuctionHasNotStarted);
+
+    harness.set_time(start_time).await;
+    buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await
+    .unwrap();
+
+    harness.set_time(start_time + 2 * DECAY_INTERVAL).await;
+    buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let auction_receipt: AuctionReceipt = harness
+        .fetch(find_auction_receipt_address(&event_ticket_address, &buyer.pubkey()).0)
+        .await;
+    assert_eq!(auction_receipt.quantity, 2);
+    assert_eq!(auction_receipt.amount_paid, 16);
+
+    // until the auction settles the rebates could go as low as the floor
+    let authority = harness.payer();
+    let destination_vault = harness.create_wallet(&authority, 0).await;
+    let withdraw =
+        |amount| instructions::withdraw(authority, event_base, destination_vault, amount);
+    let event_address = event(&event_base);
+    let unsettled: Event = harness.fetch(event_address).await;
+    assert_eq!(unsettled.rebates_owed, 8);
+    let result = harness.process(&[withdraw(9)], &[]).await;
+    assert_error(result, ErrorCode::InsufficientEventFunds);
+
+    let result = harness
+        .process(
+            &[instructions::claim_auction_rebate(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::AuctionIsNotSettled);
+
+    let result = harness
+        .process(
+            &[instructions::settle_auction(event_base, event_ticket_base)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::AuctionIsStillRunning);
+
+    harness.set_time(start_time + 3 * DECAY_INTERVAL).await;
+    harness
+        .process(
+            &[instructions::settle_auction(event_base, event_ticket_base)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.auction.unwrap().clearing_price, Some(6));
+
+    let settled: Event = harness.fetch(event_address).await;
+    assert_eq!(settled.rebates_owed, 4);
+    let result = harness.process(&[withdraw(13)], &[]).await;
+    assert_error(result, ErrorCode::InsufficientEventFunds);
+
+    let result = harness
+        .process(
+            &[instructions::settle_auction(event_base, event_ticket_base)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::AuctionIsAlreadySettled);
+
+    // settling at the floor doesn't lock up the rest of the tier
+    buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let auction_receipt: AuctionReceipt = harness
+        .fetch(find_auction_receipt_address(&event_ticket_address, &buyer.pubkey()).0)
+        .await;
+    assert_eq!(auction_receipt.quantity, 3);
+    assert_eq!(auction_receipt.amount_paid, 22);
+
+    let settled: Event = harness.fetch(event_address).await;
+    assert_eq!(settled.rebates_owed, 4);
+
+    harness
+        .process(
+            &[instructions::claim_auction_rebate(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(buyer_vault).await, 82);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 18);
+    assert_eq!(harness.token_balance(ticket_vault).await, 3);
+
+    let claimed: Event = harness.fetch(event_address).await;
+    assert_eq!(claimed.rebates_owed, 0);
+    harness.process(&[withdraw(18)], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn rejects_rebates_when_disabled() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let start_time = harness.now().await;
+
+    set_auction(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        start_time,
+        false,
+    )
+    .await
+    .unwrap();
+    buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await
+    .unwrap();
+
+    // sold out auctions can be settled before reaching the floor
+    harness
+        .process(
+            &[instructions::settle_auction(event_base, event_ticket_base)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::claim_auction_rebate(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::AuctionRebatesAreDisabled);
+}
+
+#[tokio::test]
+async fn applies_wallet_limit_to_auctions() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let start_time = harness.now().await;
+
+    harness
+        .process(
+            &[instructions::set_ticket_wallet_limit(
+                authority,
+                event_base,
+                event_ticket_base,
+                2,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    set_auction(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        start_time,
+        true,
+    )
+    .await
+    .unwrap();
+
+    buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        2,
+    )
+    .await
+    .unwrap();
+
+    let result = buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await;
+    assert_error(result, ErrorCode::WalletLimitExceeded);
+
+    // the buyer's ticket account is created on their first purchase
+    let ticket_vault = get_associated_token_address(
+        &buyer.pubkey(),
+        &ticket_mint(&event_base, &event_ticket_base),
+    );
+    assert_eq!(harness.token_balance(ticket_vault).await, 2);
+}
+
+#[tokio::test]
+async fn sponsors_auction_purchases() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let relayer = harness.create_user().await;
+    let buyer = Keypair::new();
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let start_time = harness.now().await;
+
+    harness
+        .process(
+            &[instructions::set_event_relayer(
+                authority,
+                event_base,
+                Some(relayer.pubkey()),
+                1,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    set_auction(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        start_time,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let fee_vault = harness.fee_vault;
+    harness
+        .process(
+            &[instructions::buy_auction_tickets(
+                relayer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                1,
+            )],
+            &[&relayer, &buyer],
+        )
+        .await
+        .unwrap();
+
+    // the buyer only signs for the tokens, the relayer pays for the rest
+    assert!(harness.account(buyer.pubkey()).await.is_none());
+    let sponsored: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(sponsored.relayer_owed, 1);
+}
+
+#[tokio::test]
+async fn rejects_invalid_auctions() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let now = harness.now().await;
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_auction(
+                authority,
+                event_base,
+                event_ticket_base,
+                FLOOR_PRICE,
+                START_PRICE,
+                now,
+                DECAY_INTERVAL,
+                DECAY_AMOUNT,
+                true,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuctionSchedule);
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_auction(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                START_PRICE,
+                FLOOR_PRICE,
+                now,
+                DECAY_INTERVAL,
+                DECAY_AMOUNT,
+                true,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanManageAuctions);
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+
+    let result = set_auction(&mut harness, &event_base, &event_ticket_base, now, true).await;
+    assert_error(result, ErrorCode::AuctionCanOnlyBeSetBeforeSales);
+
+    let result = buy_auction_tickets(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        1,
+    )
+    .await;
+    assert_error(result, ErrorCode::TicketIsNotSoldThroughAuction);
+
+    let result = harness
+        .process(
+            &[instructions::settle_auction(event_base, event_ticket_base)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsNotSoldThroughAuction);
+
+    let result = harness
+        .process(
+            &[instructions::claim_auction_rebate(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn keeps_auctions_and_pricing_curves_apart() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let curved_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let auction_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let now = harness.now().await;
+
+    harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                curved_ticket_base,
+                Some(PricingCurve::Linear { increment: 1 }),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = set_auction(&mut harness, &event_base, &curved_ticket_base, now, true).await;
+    assert_error(result, ErrorCode::TicketHasPricingCurve);
+
+    set_auction(&mut harness, &event_base, &auction_ticket_base, now, true)
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                auction_ticket_base,
+                Some(PricingCurve::Linear { increment: 1 }),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsSoldThroughAuction);
+
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                auction_ticket_base,
+                buyer_vault,
+                fee_vault,
+                1,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsSoldThroughAuction);
+}
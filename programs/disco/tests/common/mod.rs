@@ -0,0 +1,474 @@
+//! Shared harness for the `disco` integration tests.
+//!
+//! The suite only runs under `cargo test-bpf`, which enables the `test-bpf`
+//! feature: Anchor's CPIs can't run natively on this version of program-test.
+//! The program is loaded from `target/deploy/disco.so` and the metadata program
+//! from `tests/fixtures/mpl_token_metadata.so`, which `scripts/fetch-fixtures.sh`
+//! dumps from devnet. See the README for how to run the suite.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_spl::token::spl_token;
+use disco_client::{instructions, pda::find_program_data_address};
+use solana_program_test::{processor, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+    transport::{Result as TransportResult, TransportError},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+pub const TICKET_NAME: &str = "Tomorrowland 2022 - General";
+pub const TICKET_SYMBOL: &str = "TMRW";
+pub const TICKET_URI: &str = "https://tomorrowland.com/general.json";
+
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("disco", disco::ID, processor!(disco::entry));
+
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+
+    program_test
+}
+
+/// Starts `program_test`, making the payer the program's upgrade authority,
+/// which is who can initialize the config.
+pub async fn start_context() -> ProgramTestContext {
+    let mut context = program_test().start_with_context().await;
+    let program_data = AccountSharedData::new_data(
+        1_000_000_000,
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(context.payer.pubkey()),
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+
+    context.set_account(&find_program_data_address().0, &program_data);
+
+    context
+}
+
+/// A running validator with the protocol config initialized and an accepted mint
+/// controlled by the payer, who is also the config admin.
+pub struct Harness {
+    pub context: ProgramTestContext,
+    pub accepted_mint: Pubkey,
+    pub fee_treasury: Keypair,
+    pub fee_vault: Pubkey,
+    signatures: HashSet<Signature>,
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        let context = start_context().await;
+        let mut harness = Harness {
+            context,
+            accepted_mint: Pubkey::default(),
+            fee_treasury: Keypair::new(),
+            fee_vault: Pubkey::default(),
+            signatures: HashSet::new(),
+        };
+
+        let admin = harness.payer();
+        let fee_treasury = harness.fee_treasury.pubkey();
+
+        let accepted_mint = harness.create_mint().await;
+        harness.accepted_mint = accepted_mint;
+        harness.fee_vault = harness
+            .create_token_account(&accepted_mint, &fee_treasury)
+            .await;
+
+        harness
+            .process(
+                &[instructions::initialize_config(admin, 0, fee_treasury)],
+                &[],
+            )
+            .await
+            .unwrap();
+
+        harness
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Signs with the payer and `signers`. Transactions identical to one already
+    /// sent get a fresh blockhash, otherwise the bank returns the earlier result.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransportResult<()> {
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let mut transaction =
+            Transaction::new_with_payer(instructions, Some(&self.context.payer.pubkey()));
+        transaction.sign(&all_signers, self.context.last_blockhash);
+
+        while !self.signatures.insert(transaction.signatures[0]) {
+            self.context.last_blockhash = self
+                .context
+                .banks_client
+                .get_new_latest_blockhash(&self.context.last_blockhash)
+                .await
+                .unwrap();
+            transaction.sign(&all_signers, self.context.last_blockhash);
+        }
+
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    pub async fn account(&mut self, address: Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn fetch<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.account(address).await.unwrap();
+
+        disco_client::accounts::deserialize_owned(&account.owner, &account.data).unwrap()
+    }
+
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self.account(address).await.unwrap();
+
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn is_frozen(&mut self, address: Pubkey) -> bool {
+        let account = self.account(address).await.unwrap();
+
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .is_frozen()
+    }
+
+    pub async fn token_supply(&mut self, mint: Pubkey) -> u64 {
+        let account = self.account(mint).await.unwrap();
+
+        spl_token::state::Mint::unpack(&account.data)
+            .unwrap()
+            .supply
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let clock = Clock {
+            unix_timestamp,
+            ..self.clock().await
+        };
+
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Moves to a later slot, so the current one shows up in `SlotHashes`,
+    /// without letting the time jump.
+    pub async fn advance_slot(&mut self) {
+        let clock = self.clock().await;
+
+        self.context.warp_to_slot(clock.slot + 2).unwrap();
+        self.set_time(clock.unix_timestamp).await;
+    }
+
+    async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a keypair funded with enough SOL to pay for the accounts it creates.
+    pub async fn create_user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        let payer = self.payer();
+
+        self.process(
+            &[system_instruction::transfer(
+                &payer,
+                &user.pubkey(),
+                1_000_000_000,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        user
+    }
+
+    pub async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &payer,
+                    None,
+                    0,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        mint.pubkey()
+    }
+
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::ID,
+                    &account.pubkey(),
+                    mint,
+                    owner,
+                )
+                .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+
+        account.pubkey()
+    }
+
+    /// Creates an accepted mint account for `owner` holding `amount` tokens.
+    pub async fn create_wallet(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let accepted_mint = self.accepted_mint;
+        let wallet = self.create_token_account(&accepted_mint, owner).await;
+
+        if amount > 0 {
+            let payer = self.payer();
+
+            self.process(
+                &[spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &accepted_mint,
+                    &wallet,
+                    &payer,
+                    &[],
+                    amount,
+                )
+                .unwrap()],
+                &[],
+            )
+            .await
+            .unwrap();
+        }
+
+        wallet
+    }
+
+    /// Creates the associated ticket account of `owner` on the ticket mint of an
+    /// event ticket, unless it already exists.
+    pub async fn create_ticket_vault(
+        &mut self,
+        event_base: &Pubkey,
+        event_ticket_base: &Pubkey,
+        owner: &Pubkey,
+    ) -> Pubkey {
+        let ticket_mint = ticket_mint(event_base, event_ticket_base);
+        let ticket_vault = get_associated_token_address(owner, &ticket_mint);
+
+        if self.account(ticket_vault).await.is_none() {
+            let payer = self.payer();
+
+            self.process(
+                &[create_associated_token_account(&payer, owner, &ticket_mint)],
+                &[],
+            )
+            .await
+            .unwrap();
+        }
+
+        ticket_vault
+    }
+
+    /// Creates an event owned by the payer and returns its base.
+    pub async fn create_event(&mut self) -> Pubkey {
+        let event_base = Keypair::new().pubkey();
+        let authority = self.payer();
+        let accepted_mint = self.accepted_mint;
+
+        self.process(
+            &[instructions::create_event(
+                authority,
+                event_base,
+                accepted_mint,
+                "Tomorrowland 2022".to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        event_base
+    }
+
+    /// Creates an event ticket on an event owned by the payer and returns its base.
+    pub async fn create_event_ticket(
+        &mut self,
+        event_base: &Pubkey,
+        price: u32,
+        quantity: u32,
+    ) -> Pubkey {
+        let event_ticket_base = Keypair::new().pubkey();
+        let authority = self.payer();
+
+        self.process(
+            &[instructions::create_event_ticket(
+                authority,
+                *event_base,
+                event_ticket_base,
+                TICKET_NAME.to_string(),
+                TICKET_SYMBOL.to_string(),
+                TICKET_URI.to_string(),
+                price,
+                quantity,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        event_ticket_base
+    }
+
+    /// Adds the payer as a collaborator of an event it owns, so the payer can sign
+    /// check-ins as the collaborator.
+    pub async fn add_self_as_collaborator(&mut self, event_base: &Pubkey) {
+        let authority = self.payer();
+
+        self.process(
+            &[instructions::create_collaborator(
+                authority,
+                *event_base,
+                authority,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Buys tickets for `buyer`, returning the associated ticket vault they were
+    /// minted to.
+    pub async fn buy_tickets(
+        &mut self,
+        event_base: &Pubkey,
+        event_ticket_base: &Pubkey,
+        buyer: &Keypair,
+        buyer_vault: &Pubkey,
+        ticket_quantity: u32,
+    ) -> Pubkey {
+        let fee_vault = self.fee_vault;
+
+        self.process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                *buyer_vault,
+                fee_vault,
+                ticket_quantity,
+            )],
+            &[buyer],
+        )
+        .await
+        .unwrap();
+
+        get_associated_token_address(&buyer.pubkey(), &ticket_mint(event_base, event_ticket_base))
+    }
+}
+
+pub fn event(event_base: &Pubkey) -> Pubkey {
+    disco_client::pda::find_event_address(event_base).0
+}
+
+pub fn event_vault(event_base: &Pubkey) -> Pubkey {
+    disco_client::pda::find_event_vault_address(&event(event_base)).0
+}
+
+pub fn event_ticket(event_base: &Pubkey, event_ticket_base: &Pubkey) -> Pubkey {
+    disco_client::pda::find_event_ticket_address(&event(event_base), event_ticket_base).0
+}
+
+pub fn ticket_mint(event_base: &Pubkey, event_ticket_base: &Pubkey) -> Pubkey {
+    let event = event(event_base);
+    let event_ticket = disco_client::pda::find_event_ticket_address(&event, event_ticket_base).0;
+
+    disco_client::pda::find_ticket_mint_address(&event, &event_ticket).0
+}
+
+pub fn attendance_mint(event_base: &Pubkey, event_ticket_base: &Pubkey) -> Pubkey {
+    let event = event(event_base);
+    let event_ticket = disco_client::pda::find_event_ticket_address(&event, event_ticket_base).0;
+
+    disco_client::pda::find_attendance_mint_address(&event, &event_ticket).0
+}
+
+/// Asserts a transaction failed with the given program or framework error.
+pub fn assert_error(result: TransportResult<()>, error: impl Into<u32>) {
+    let code = error.into();
+
+    match result {
+        Err(TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code, "expected error {}, got {}", code, actual),
+        other => panic!("expected error {}, got {:?}", code, other),
+    }
+}
@@ -0,0 +1,326 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{Config, Organizer},
+    instructions,
+    pda::{find_config_address, find_organizer_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn initializes_config() {
+    let mut harness = Harness::start().await;
+
+    let config: Config = harness.fetch(find_config_address().0).await;
+    assert_eq!(config.admin, harness.payer());
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.fee_treasury, harness.fee_treasury.pubkey());
+    assert!(!config.paused);
+}
+
+#[tokio::test]
+async fn rejects_config_initialized_by_others() {
+    let mut context = start_context().await;
+    let payer = context.payer.pubkey();
+    let stranger = Keypair::new();
+    let transaction = solana_sdk::transaction::Transaction::new_signed_with_payer(
+        &[
+            solana_sdk::system_instruction::transfer(&payer, &stranger.pubkey(), 1_000_000_000),
+            instructions::initialize_config(stranger.pubkey(), 0, Keypair::new().pubkey()),
+        ],
+        Some(&payer),
+        &[&context.payer, &stranger],
+        context.last_blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(transaction).await;
+    assert_error(result, ErrorCode::OnlyUpgradeAuthorityCanInitializeConfig);
+}
+
+#[tokio::test]
+async fn rejects_invalid_initial_fee_rate() {
+    let mut context = start_context().await;
+    let admin = context.payer.pubkey();
+    let transaction = solana_sdk::transaction::Transaction::new_signed_with_payer(
+        &[instructions::initialize_config(
+            admin,
+            10_001,
+            Keypair::new().pubkey(),
+        )],
+        Some(&admin),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context.banks_client.process_transaction(transaction).await;
+    assert_error(result, ErrorCode::InvalidFeeRate);
+}
+
+#[tokio::test]
+async fn updates_config_admin() {
+    let mut harness = Harness::start().await;
+    let admin = harness.payer();
+    let new_admin = harness.create_user().await;
+
+    harness
+        .process(
+            &[instructions::set_config_admin(admin, new_admin.pubkey())],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let config: Config = harness.fetch(find_config_address().0).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+
+    let result = harness
+        .process(&[instructions::set_config_admin(admin, admin)], &[])
+        .await;
+    assert_error(result, ErrorCode::OnlyConfigAdminCanUpdateConfig);
+}
+
+#[tokio::test]
+async fn splits_platform_fee() {
+    let mut harness = Harness::start().await;
+    let admin = harness.payer();
+    let fee_treasury = harness.fee_treasury.pubkey();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 10, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+
+    let result = harness
+        .process(
+            &[instructions::set_platform_fee(admin, 10_001, fee_treasury)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidFeeRate);
+
+    let stranger = harness.create_user().await;
+    let result = harness
+        .process(
+            &[instructions::set_platform_fee(
+                stranger.pubkey(),
+                1_000,
+                fee_treasury,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyConfigAdminCanUpdateConfig);
+
+    harness
+        .process(
+            &[instructions::set_platform_fee(admin, 1_000, fee_treasury)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 5)
+        .await;
+
+    let fee_vault = harness.fee_vault;
+    assert_eq!(harness.token_balance(buyer_vault).await, 50);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 45);
+    assert_eq!(harness.token_balance(fee_vault).await, 5);
+
+    // the fee is taken on every kind of sale
+    harness
+        .process(
+            &[
+                instructions::reserve_tickets(buyer.pubkey(), event_base, event_ticket_base, 2),
+                instructions::complete_reservation(
+                    buyer.pubkey(),
+                    buyer.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    buyer_vault,
+                    fee_vault,
+                ),
+            ],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(buyer_vault).await, 30);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 63);
+    assert_eq!(harness.token_balance(fee_vault).await, 7);
+
+    // affiliates are paid out of the event's share
+    let affiliate_base = harness.create_user().await;
+    let affiliate_vault = harness.create_wallet(&affiliate_base.pubkey(), 0).await;
+    harness
+        .process(
+            &[
+                instructions::create_affiliate(admin, event_base, affiliate_base.pubkey(), 1_000),
+                instructions::buy_tickets_with_affiliate(
+                    buyer.pubkey(),
+                    buyer.pubkey(),
+                    buyer.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    buyer_vault,
+                    fee_vault,
+                    affiliate_base.pubkey(),
+                    affiliate_vault,
+                    2,
+                ),
+            ],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(buyer_vault).await, 10);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 80);
+    assert_eq!(harness.token_balance(fee_vault).await, 9);
+    assert_eq!(harness.token_balance(affiliate_vault).await, 1);
+
+    // a full commission takes the whole share and nothing else
+    let partner_base = harness.create_user().await;
+    let partner_vault = harness.create_wallet(&partner_base.pubkey(), 0).await;
+    harness
+        .process(
+            &[
+                instructions::create_affiliate(admin, event_base, partner_base.pubkey(), 10_000),
+                instructions::buy_tickets_with_affiliate(
+                    buyer.pubkey(),
+                    buyer.pubkey(),
+                    buyer.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    buyer_vault,
+                    fee_vault,
+                    partner_base.pubkey(),
+                    partner_vault,
+                    1,
+                ),
+            ],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 80);
+    assert_eq!(harness.token_balance(fee_vault).await, 10);
+    assert_eq!(harness.token_balance(partner_vault).await, 9);
+}
+
+#[tokio::test]
+async fn rejects_fee_vault_of_another_owner() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 10, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                buyer_vault,
+                1,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidFeeVault);
+}
+
+#[tokio::test]
+async fn pauses_program() {
+    let mut harness = Harness::start().await;
+    let admin = harness.payer();
+    let stranger = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::set_paused(stranger.pubkey(), true)],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyConfigAdminCanUpdateConfig);
+
+    harness
+        .process(&[instructions::set_paused(admin, true)], &[])
+        .await
+        .unwrap();
+
+    let accepted_mint = harness.accepted_mint;
+    let result = harness
+        .process(
+            &[instructions::create_event(
+                admin,
+                Keypair::new().pubkey(),
+                accepted_mint,
+                "Tomorrowland 2022".to_string(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::ProgramPaused);
+
+    harness
+        .process(&[instructions::set_paused(admin, false)], &[])
+        .await
+        .unwrap();
+
+    harness.create_event().await;
+}
+
+#[tokio::test]
+async fn verifies_organizer() {
+    let mut harness = Harness::start().await;
+    let admin = harness.payer();
+    let organizer_authority = harness.create_user().await;
+    let organizer = find_organizer_address(&organizer_authority.pubkey()).0;
+
+    harness
+        .process(
+            &[instructions::create_organizer(
+                organizer_authority.pubkey(),
+                "Tomorrowland".to_string(),
+                "https://tomorrowland.com".to_string(),
+            )],
+            &[&organizer_authority],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::verify_organizer(
+                organizer_authority.pubkey(),
+                organizer,
+                true,
+            )],
+            &[&organizer_authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyConfigAdminCanVerifyOrganizers);
+
+    harness
+        .process(
+            &[instructions::verify_organizer(admin, organizer, true)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let organizer: Organizer = harness.fetch(organizer).await;
+    assert!(organizer.verified);
+}
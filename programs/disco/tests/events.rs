@@ -0,0 +1,621 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{accounts::Event, instructions, pda::find_collaborator_address};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+const POAP_NAME: &str = "Tomorrowland 2022 - POAP";
+const POAP_SYMBOL: &str = "TMRWP";
+const POAP_URI: &str = "https://tomorrowland.com/poap.json";
+
+#[tokio::test]
+async fn creates_event() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+
+    let event: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(event.event_title, "Tomorrowland 2022");
+    assert_eq!(event.authority, harness.payer());
+    assert_eq!(event.accepted_mint, harness.accepted_mint);
+    assert_eq!(event.organizer, None);
+    assert_eq!(event.total_capacity, 0);
+    assert!(!event.paused);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 0);
+}
+
+#[tokio::test]
+async fn pauses_event() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let stranger = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::set_event_paused(
+                stranger.pubkey(),
+                event_base,
+                true,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanPauseEvent);
+
+    let relayer_vault = harness.create_wallet(&authority, 0).await;
+    harness
+        .process(
+            &[instructions::set_event_relayer(
+                authority,
+                event_base,
+                Some(authority),
+                1,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(
+            &[instructions::set_event_paused(authority, event_base, true)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::set_event_relayer(
+                authority, event_base, None, 0,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventPaused);
+
+    let result = harness
+        .process(
+            &[instructions::claim_relayer_reimbursement(
+                authority,
+                event_base,
+                relayer_vault,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventPaused);
+
+    let result = harness
+        .process(
+            &[instructions::create_event_ticket(
+                authority,
+                event_base,
+                Keypair::new().pubkey(),
+                TICKET_NAME.to_string(),
+                TICKET_SYMBOL.to_string(),
+                TICKET_URI.to_string(),
+                5,
+                10,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventPaused);
+
+    let result = harness
+        .process(
+            &[instructions::update_event_schedule(
+                authority,
+                event_base,
+                1_656_000_000,
+                1_656_300_000,
+                "Europe/Brussels".to_string(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventPaused);
+
+    let result = harness
+        .process(
+            &[instructions::update_event_details(
+                authority,
+                event_base,
+                "Schommelei 1, 2850 Boom".to_string(),
+                vec!["techno".to_string()],
+                "https://tomorrowland.com/event.json".to_string(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventPaused);
+
+    harness
+        .process(
+            &[instructions::set_event_paused(authority, event_base, false)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness.create_event_ticket(&event_base, 5, 10).await;
+}
+
+#[tokio::test]
+async fn manages_collaborators() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let collaborator_base = Keypair::new().pubkey();
+    let collaborator = find_collaborator_address(&event(&event_base), &collaborator_base).0;
+    let stranger = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::create_collaborator(
+                stranger.pubkey(),
+                event_base,
+                collaborator_base,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanCreateCollaborators);
+
+    harness
+        .process(
+            &[instructions::create_collaborator(
+                authority,
+                event_base,
+                collaborator_base,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(harness.account(collaborator).await.is_some());
+
+    let result = harness
+        .process(
+            &[instructions::delete_collaborator(
+                stranger.pubkey(),
+                event_base,
+                collaborator_base,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanDeleteCollaborators);
+
+    harness
+        .process(
+            &[instructions::delete_collaborator(
+                authority,
+                event_base,
+                collaborator_base,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(harness.account(collaborator).await.is_none());
+}
+
+#[tokio::test]
+async fn withdraws_event_funds() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let destination_vault = harness.create_wallet(&authority, 0).await;
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 4)
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::withdraw(
+                buyer.pubkey(),
+                event_base,
+                buyer_vault,
+                20,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanWithdraw);
+
+    let result = harness
+        .process(
+            &[instructions::withdraw(
+                authority,
+                event_base,
+                destination_vault,
+                21,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InsufficientEventFunds);
+
+    harness
+        .process(
+            &[instructions::withdraw(
+                authority,
+                event_base,
+                destination_vault,
+                15,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(destination_vault).await, 15);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 5);
+}
+
+#[tokio::test]
+async fn validates_event_titles() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let accepted_mint = harness.accepted_mint;
+
+    for (event_title, error) in [
+        (
+            "a".repeat(Event::MAX_TITLE_LENGTH + 1),
+            ErrorCode::EventTitleTooLong,
+        ),
+        ("   ".to_string(), ErrorCode::InvalidEventTitle),
+        (
+            "Tomorrowland\n2022".to_string(),
+            ErrorCode::InvalidEventTitle,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::create_event(
+                    authority,
+                    Keypair::new().pubkey(),
+                    accepted_mint,
+                    event_title,
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+
+    let event_title = "Festival Internacional de Música Electrónica — Edición 2022";
+    let event_base = Keypair::new().pubkey();
+    harness
+        .process(
+            &[instructions::create_event(
+                authority,
+                event_base,
+                accepted_mint,
+                event_title.to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event_address = event(&event_base);
+    let account = harness.account(event_address).await.unwrap();
+    assert_eq!(account.data.len(), Event::size(event_title));
+
+    let created: Event = harness.fetch(event_address).await;
+    assert_eq!(created.event_title, event_title);
+}
+
+#[tokio::test]
+async fn validates_ticket_strings() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+
+    for (ticket_name, ticket_symbol, ticket_uri, error) in [
+        (
+            "a".repeat(33),
+            TICKET_SYMBOL.to_string(),
+            TICKET_URI.to_string(),
+            ErrorCode::TicketNameTooLong,
+        ),
+        (
+            " ".to_string(),
+            TICKET_SYMBOL.to_string(),
+            TICKET_URI.to_string(),
+            ErrorCode::InvalidTicketName,
+        ),
+        (
+            TICKET_NAME.to_string(),
+            "A".repeat(11),
+            TICKET_URI.to_string(),
+            ErrorCode::TicketSymbolTooLong,
+        ),
+        (
+            TICKET_NAME.to_string(),
+            "TMRW-22".to_string(),
+            TICKET_URI.to_string(),
+            ErrorCode::InvalidTicketSymbol,
+        ),
+        (
+            TICKET_NAME.to_string(),
+            TICKET_SYMBOL.to_string(),
+            format!("https://{}", "a".repeat(193)),
+            ErrorCode::TicketUriTooLong,
+        ),
+        (
+            TICKET_NAME.to_string(),
+            TICKET_SYMBOL.to_string(),
+            "https://tomorrowland.com/general ticket.json".to_string(),
+            ErrorCode::InvalidTicketUri,
+        ),
+        (
+            TICKET_NAME.to_string(),
+            TICKET_SYMBOL.to_string(),
+            String::new(),
+            ErrorCode::InvalidTicketUri,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::create_event_ticket(
+                    authority,
+                    event_base,
+                    Keypair::new().pubkey(),
+                    ticket_name,
+                    ticket_symbol,
+                    ticket_uri,
+                    5,
+                    10,
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+}
+
+#[tokio::test]
+async fn validates_proof_of_attendance_strings() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+
+    for (poap_name, poap_symbol, poap_uri, error) in [
+        (
+            "a".repeat(33),
+            POAP_SYMBOL.to_string(),
+            POAP_URI.to_string(),
+            ErrorCode::PoapNameTooLong,
+        ),
+        (
+            "POAP\n".to_string(),
+            POAP_SYMBOL.to_string(),
+            POAP_URI.to_string(),
+            ErrorCode::InvalidPoapName,
+        ),
+        (
+            POAP_NAME.to_string(),
+            "A".repeat(11),
+            POAP_URI.to_string(),
+            ErrorCode::PoapSymbolTooLong,
+        ),
+        (
+            POAP_NAME.to_string(),
+            "TMRW-P".to_string(),
+            POAP_URI.to_string(),
+            ErrorCode::InvalidPoapSymbol,
+        ),
+        (
+            POAP_NAME.to_string(),
+            POAP_SYMBOL.to_string(),
+            format!("https://{}", "a".repeat(193)),
+            ErrorCode::PoapUriTooLong,
+        ),
+        (
+            POAP_NAME.to_string(),
+            POAP_SYMBOL.to_string(),
+            String::new(),
+            ErrorCode::InvalidPoapUri,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::create_proof_of_attendance(
+                    authority,
+                    event_base,
+                    event_ticket_base,
+                    poap_name,
+                    poap_symbol,
+                    poap_uri,
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+}
+
+#[tokio::test]
+async fn updates_event_metadata() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let stranger = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::update_event_schedule(
+                stranger.pubkey(),
+                event_base,
+                1_656_000_000,
+                1_656_300_000,
+                "Europe/Brussels".to_string(),
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanUpdateEvent);
+
+    for (start_time, end_time, timezone, error) in [
+        (
+            1_656_300_000,
+            1_656_000_000,
+            "Europe/Brussels",
+            ErrorCode::InvalidEventSchedule,
+        ),
+        (
+            0,
+            1_656_000_000,
+            "Europe/Brussels",
+            ErrorCode::InvalidEventSchedule,
+        ),
+        (
+            1_656_000_000,
+            1_656_300_000,
+            "Europe/Brussels/Boom/De_Schorre/Main",
+            ErrorCode::TimezoneTooLong,
+        ),
+        (
+            1_656_000_000,
+            1_656_300_000,
+            "Europe Brussels",
+            ErrorCode::InvalidTimezone,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::update_event_schedule(
+                    authority,
+                    event_base,
+                    start_time,
+                    end_time,
+                    timezone.to_string(),
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+
+    harness
+        .process(
+            &[instructions::update_event_schedule(
+                authority,
+                event_base,
+                1_656_000_000,
+                1_656_300_000,
+                "Europe/Brussels".to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let tag = |tag: &str| tag.to_string();
+    for (location, tags, metadata_uri, error) in [
+        ("a".repeat(65), vec![], "", ErrorCode::LocationTooLong),
+        (
+            "Boom\nBelgium".to_string(),
+            vec![],
+            "",
+            ErrorCode::InvalidLocation,
+        ),
+        (
+            String::new(),
+            vec![tag("edm"); 6],
+            "",
+            ErrorCode::TooManyTags,
+        ),
+        (String::new(), vec![tag("EDM")], "", ErrorCode::InvalidTag),
+        (String::new(), vec![tag("")], "", ErrorCode::InvalidTag),
+        (
+            String::new(),
+            vec![tag("electronic-dance-music")],
+            "",
+            ErrorCode::InvalidTag,
+        ),
+        (
+            String::new(),
+            vec![],
+            "https://tomorrowland.com/event 2022.json",
+            ErrorCode::InvalidMetadataUri,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::update_event_details(
+                    authority,
+                    event_base,
+                    location,
+                    tags,
+                    metadata_uri.to_string(),
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+
+    let result = harness
+        .process(
+            &[instructions::update_event_details(
+                authority,
+                event_base,
+                String::new(),
+                vec![],
+                format!("https://{}", "a".repeat(193)),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::MetadataUriTooLong);
+
+    harness
+        .process(
+            &[instructions::update_event_details(
+                authority,
+                event_base,
+                "u155sz".to_string(),
+                vec![tag("festival"), tag("edm")],
+                "https://tomorrowland.com/event.json".to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(event.start_time, 1_656_000_000);
+    assert_eq!(event.end_time, 1_656_300_000);
+    assert_eq!(event.timezone, "Europe/Brussels");
+    assert_eq!(event.location, "u155sz");
+    assert_eq!(event.tags, vec!["festival", "edm"]);
+    assert_eq!(event.metadata_uri, "https://tomorrowland.com/event.json");
+
+    // metadata fields are reserved up front, so filling them all still fits
+    harness
+        .process(
+            &[instructions::update_event_details(
+                authority,
+                event_base,
+                "a".repeat(Event::MAX_LOCATION_LENGTH),
+                vec!["a".repeat(Event::MAX_TAG_LENGTH); Event::MAX_TAGS],
+                "a".repeat(Event::MAX_METADATA_URI_LENGTH),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+}
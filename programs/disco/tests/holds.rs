@@ -0,0 +1,224 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{EventTicket, TicketHold, WalletPurchase},
+    instructions,
+    pda::{find_ticket_hold_address, find_wallet_purchase_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+async fn reserve_tickets(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    holder: &Keypair,
+    ticket_quantity: u32,
+) -> solana_sdk::transport::Result<()> {
+    harness
+        .process(
+            &[instructions::reserve_tickets(
+                holder.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                ticket_quantity,
+            )],
+            &[holder],
+        )
+        .await
+}
+
+#[tokio::test]
+async fn completes_reservations() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 3).await;
+    let holder = harness.create_user().await;
+    let holder_vault = harness.create_wallet(&holder.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &holder.pubkey())
+        .await;
+    let other = harness.create_user().await;
+
+    reserve_tickets(&mut harness, &event_base, &event_ticket_base, &holder, 2)
+        .await
+        .unwrap();
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let ticket_hold_address = find_ticket_hold_address(&event_ticket_address, &holder.pubkey()).0;
+    let ticket_hold: TicketHold = harness.fetch(ticket_hold_address).await;
+    assert_eq!(ticket_hold.quantity, 2);
+    assert_eq!(
+        ticket_hold.expires_at,
+        harness.now().await + TicketHold::DURATION
+    );
+
+    let result = reserve_tickets(&mut harness, &event_base, &event_ticket_base, &other, 2).await;
+    assert_error(result, ErrorCode::NotEnoughTicketsAvailable);
+
+    let result = harness
+        .process(
+            &[instructions::release_expired_hold(
+                event_base,
+                event_ticket_base,
+                holder.pubkey(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketHoldHasNotExpired);
+
+    harness
+        .process(
+            &[instructions::complete_reservation(
+                holder.pubkey(),
+                holder.pubkey(),
+                event_base,
+                event_ticket_base,
+                holder_vault,
+                harness.fee_vault,
+            )],
+            &[&holder],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.held, 0);
+    assert_eq!(event_ticket.sold, 2);
+    assert_eq!(harness.token_balance(ticket_vault).await, 2);
+    assert_eq!(harness.token_balance(holder_vault).await, 90);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 10);
+    assert!(harness.account(ticket_hold_address).await.is_none());
+}
+
+#[tokio::test]
+async fn releases_expired_holds() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 3).await;
+    let holder = harness.create_user().await;
+    let holder_vault = harness.create_wallet(&holder.pubkey(), 100).await;
+
+    reserve_tickets(&mut harness, &event_base, &event_ticket_base, &holder, 3)
+        .await
+        .unwrap();
+
+    let now = harness.now().await;
+    harness.set_time(now + TicketHold::DURATION + 1).await;
+
+    let result = harness
+        .process(
+            &[instructions::complete_reservation(
+                holder.pubkey(),
+                holder.pubkey(),
+                event_base,
+                event_ticket_base,
+                holder_vault,
+                harness.fee_vault,
+            )],
+            &[&holder],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketHoldHasExpired);
+
+    harness
+        .process(
+            &[instructions::release_expired_hold(
+                event_base,
+                event_ticket_base,
+                holder.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.held, 0);
+    assert_eq!(event_ticket.available(), 3);
+
+    let ticket_hold_address = find_ticket_hold_address(&event_ticket_address, &holder.pubkey()).0;
+    assert!(harness.account(ticket_hold_address).await.is_none());
+}
+
+#[tokio::test]
+async fn applies_wallet_limit_to_holds() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let holder = harness.create_user().await;
+    let holder_vault = harness.create_wallet(&holder.pubkey(), 100).await;
+
+    harness
+        .process(
+            &[instructions::set_ticket_wallet_limit(
+                authority,
+                event_base,
+                event_ticket_base,
+                2,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = reserve_tickets(&mut harness, &event_base, &event_ticket_base, &holder, 3).await;
+    assert_error(result, ErrorCode::WalletLimitExceeded);
+
+    reserve_tickets(&mut harness, &event_base, &event_ticket_base, &holder, 2)
+        .await
+        .unwrap();
+
+    // the hold already takes up the whole limit
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                holder.pubkey(),
+                holder.pubkey(),
+                holder.pubkey(),
+                event_base,
+                event_ticket_base,
+                holder_vault,
+                fee_vault,
+                1,
+            )],
+            &[&holder],
+        )
+        .await;
+    assert_error(result, ErrorCode::WalletLimitExceeded);
+
+    let now = harness.now().await;
+    harness.set_time(now + TicketHold::DURATION + 1).await;
+    harness
+        .process(
+            &[instructions::release_expired_hold(
+                event_base,
+                event_ticket_base,
+                holder.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let wallet_purchase: WalletPurchase = harness
+        .fetch(find_wallet_purchase_address(&event_ticket_address, &holder.pubkey()).0)
+        .await;
+    assert_eq!(wallet_purchase.held, 0);
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &holder, &holder_vault, 2)
+        .await;
+}
@@ -0,0 +1,326 @@
+#![cfg(feature = "test-bpf")]
+
+//! Runs random sequences of purchases, comps, check-ins, waitlist releases and
+//! withdrawals against a single ticket tier, checking the on-chain accounting
+//! against a model after every step.
+
+mod common;
+
+use common::*;
+use std::collections::VecDeque;
+
+use disco_client::{
+    accounts::{Event, EventTicket},
+    instructions,
+    pda::{find_waitlist_address, find_waitlist_vault_address},
+};
+use proptest::prelude::*;
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const PRICE: u32 = 5;
+const QUANTITY: u32 = 10;
+const HOLDERS: usize = 3;
+const FUNDS: u64 = 60;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Buy { holder: usize, quantity: u32 },
+    Comp { holder: usize, quantity: u32 },
+    CheckIn { holder: usize, quantity: u32 },
+    Join { holder: usize },
+    Release { holder: usize },
+    Withdraw { amount: u64 },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (0..HOLDERS, 0..6u32).prop_map(|(holder, quantity)| Op::Buy { holder, quantity }),
+        1 => (0..HOLDERS, 0..6u32).prop_map(|(holder, quantity)| Op::Comp { holder, quantity }),
+        2 => (0..HOLDERS, 0..6u32)
+            .prop_map(|(holder, quantity)| Op::CheckIn { holder, quantity }),
+        1 => (0..HOLDERS).prop_map(|holder| Op::Join { holder }),
+        2 => (0..HOLDERS).prop_map(|holder| Op::Release { holder }),
+        1 => (0..50u64).prop_map(|amount| Op::Withdraw { amount }),
+    ]
+}
+
+struct Holder {
+    owner: Keypair,
+    buyer_vault: Pubkey,
+    ticket_vault: Pubkey,
+    funds: u64,
+    tickets: u64,
+    purchased: u32,
+    comps: u32,
+}
+
+#[derive(Default)]
+struct Model {
+    sold: u32,
+    comps: u32,
+    used: u32,
+    revenue: u64,
+    withdrawn: u64,
+    waitlist: VecDeque<usize>,
+    waitlist_head: u64,
+}
+
+impl Model {
+    fn available(&self) -> u32 {
+        QUANTITY - self.sold - self.comps
+    }
+}
+
+async fn run(ops: Vec<Op>) {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness
+        .create_event_ticket(&event_base, PRICE, QUANTITY)
+        .await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let accepted_mint = harness.accepted_mint;
+    harness
+        .process(
+            &[instructions::create_waitlist(
+                authority,
+                event_base,
+                event_ticket_base,
+                accepted_mint,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    let waitlist_vault = find_waitlist_vault_address(
+        &find_waitlist_address(&event_ticket(&event_base, &event_ticket_base)).0,
+    )
+    .0;
+    let destination_vault = harness.create_wallet(&authority, 0).await;
+    let fee_vault = harness.fee_vault;
+
+    let mut holders = Vec::new();
+    for _ in 0..HOLDERS {
+        let owner = harness.create_user().await;
+        let buyer_vault = harness.create_wallet(&owner.pubkey(), FUNDS).await;
+        let ticket_vault = harness
+            .create_ticket_vault(&event_base, &event_ticket_base, &owner.pubkey())
+            .await;
+
+        holders.push(Holder {
+            owner,
+            buyer_vault,
+            ticket_vault,
+            funds: FUNDS,
+            tickets: 0,
+            purchased: 0,
+            comps: 0,
+        });
+    }
+
+    let mut model = Model::default();
+
+    for op in ops {
+        let (instruction, signer, expected): (Instruction, Option<&Keypair>, bool) = match op {
+            Op::Buy { holder, quantity } => {
+                let holder = &holders[holder];
+                let cost = u64::from(PRICE * quantity);
+
+                (
+                    instructions::buy_tickets(
+                        holder.owner.pubkey(),
+                        holder.owner.pubkey(),
+                        holder.owner.pubkey(),
+                        event_base,
+                        event_ticket_base,
+                        holder.buyer_vault,
+                        fee_vault,
+                        quantity,
+                    ),
+                    Some(&holder.owner),
+                    quantity > 0 && quantity <= model.available() && cost <= holder.funds,
+                )
+            }
+            Op::Comp { holder, quantity } => (
+                instructions::issue_comp_tickets(
+                    authority,
+                    event_base,
+                    event_ticket_base,
+                    holders[holder].owner.pubkey(),
+                    quantity,
+                ),
+                None,
+                quantity > 0 && quantity <= model.available(),
+            ),
+            Op::CheckIn { holder, quantity } => {
+                let holder = &holders[holder];
+
+                (
+                    instructions::check_in(
+                        authority,
+                        authority,
+                        holder.owner.pubkey(),
+                        event_base,
+                        event_ticket_base,
+                        holder.ticket_vault,
+                        quantity,
+                    ),
+                    Some(&holder.owner),
+                    quantity > 0 && u64::from(quantity) <= holder.tickets,
+                )
+            }
+            Op::Join { holder } => {
+                let holder = &holders[holder];
+
+                (
+                    instructions::join_waitlist(
+                        holder.owner.pubkey(),
+                        holder.owner.pubkey(),
+                        event_base,
+                        event_ticket_base,
+                        model.waitlist_head + model.waitlist.len() as u64,
+                        holder.buyer_vault,
+                    ),
+                    Some(&holder.owner),
+                    model.available() == 0 && u64::from(PRICE) <= holder.funds,
+                )
+            }
+            Op::Release { holder } => {
+                let waiter = model.waitlist.front().map_or(holder, |waiter| *waiter);
+                let holder = &holders[holder];
+
+                (
+                    instructions::release_ticket(
+                        holder.owner.pubkey(),
+                        event_base,
+                        event_ticket_base,
+                        model.waitlist_head,
+                        holders[waiter].owner.pubkey(),
+                        holder.ticket_vault,
+                        holders[waiter].ticket_vault,
+                        holder.buyer_vault,
+                        fee_vault,
+                    ),
+                    Some(&holder.owner),
+                    !model.waitlist.is_empty()
+                        && holder.purchased > 0
+                        && holder.tickets > u64::from(holder.comps),
+                )
+            }
+            Op::Withdraw { amount } => (
+                instructions::withdraw(authority, event_base, destination_vault, amount),
+                None,
+                amount <= model.revenue - model.withdrawn,
+            ),
+        };
+
+        let signers: Vec<&Keypair> = signer.into_iter().collect();
+        let result = harness.process(&[instruction], &signers).await;
+        assert_eq!(result.is_ok(), expected, "{:?} returned {:?}", op, result);
+
+        if expected {
+            match op {
+                Op::Buy { holder, quantity } => {
+                    let cost = u64::from(PRICE * quantity);
+
+                    model.sold += quantity;
+                    model.revenue += cost;
+                    holders[holder].funds -= cost;
+                    holders[holder].tickets += u64::from(quantity);
+                    holders[holder].purchased += quantity;
+                }
+                Op::Comp { holder, quantity } => {
+                    model.comps += quantity;
+                    holders[holder].tickets += u64::from(quantity);
+                    holders[holder].comps += quantity;
+                }
+                Op::CheckIn { holder, quantity } => {
+                    model.used += quantity;
+                    holders[holder].tickets -= u64::from(quantity);
+                }
+                Op::Join { holder } => {
+                    model.waitlist.push_back(holder);
+                    holders[holder].funds -= u64::from(PRICE);
+                    holders[holder].purchased += 1;
+                }
+                Op::Release { holder } => {
+                    // the ticket changes hands, so sold, used and the supply
+                    // stay the same, and the waitlisted payment settles the
+                    // releaser's refund without touching the event vault
+                    let waiter = model.waitlist.pop_front().unwrap();
+
+                    model.waitlist_head += 1;
+                    holders[holder].funds += u64::from(PRICE);
+                    holders[holder].tickets -= 1;
+                    holders[holder].purchased -= 1;
+                    holders[waiter].tickets += 1;
+                }
+                Op::Withdraw { amount } => model.withdrawn += amount,
+            }
+        }
+
+        let event_ticket: EventTicket = harness
+            .fetch(event_ticket(&event_base, &event_ticket_base))
+            .await;
+        assert!(event_ticket.used <= event_ticket.sold + event_ticket.comps);
+        assert!(event_ticket.sold + event_ticket.comps <= event_ticket.quantity);
+        assert_eq!(event_ticket.sold, model.sold);
+        assert_eq!(event_ticket.comps, model.comps);
+        assert_eq!(event_ticket.used, model.used);
+        assert_eq!(event_ticket.waitlisted as usize, model.waitlist.len());
+
+        let event: Event = harness.fetch(event(&event_base)).await;
+        assert_eq!(event.total_sold, model.sold);
+
+        let supply = harness
+            .token_supply(ticket_mint(&event_base, &event_ticket_base))
+            .await;
+        assert_eq!(
+            supply,
+            u64::from(event_ticket.sold + event_ticket.comps - event_ticket.used)
+        );
+
+        let mut tickets = 0;
+        for holder in &holders {
+            let balance = harness.token_balance(holder.ticket_vault).await;
+            assert_eq!(balance, holder.tickets);
+            assert_eq!(
+                harness.token_balance(holder.buyer_vault).await,
+                holder.funds
+            );
+            tickets += balance;
+        }
+        assert_eq!(tickets, supply);
+
+        assert_eq!(
+            harness.token_balance(event_vault(&event_base)).await,
+            model.revenue - model.withdrawn
+        );
+        assert_eq!(
+            harness.token_balance(destination_vault).await,
+            model.withdrawn
+        );
+        assert_eq!(
+            harness.token_balance(waitlist_vault).await,
+            u64::from(PRICE) * model.waitlist.len() as u64
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn keeps_ticket_accounting_consistent(ops in prop::collection::vec(op(), 1..24)) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run(ops));
+    }
+}
@@ -0,0 +1,337 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{Event, Organizer, Series},
+    instructions,
+    pda::{find_organizer_address, find_organizer_event_base_address, find_series_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+async fn create_organizer(harness: &mut Harness, authority: &Keypair) -> Pubkey {
+    harness
+        .process(
+            &[instructions::create_organizer(
+                authority.pubkey(),
+                "Tomorrowland".to_string(),
+                "https://tomorrowland.com".to_string(),
+            )],
+            &[authority],
+        )
+        .await
+        .unwrap();
+
+    find_organizer_address(&authority.pubkey()).0
+}
+
+async fn create_organizer_event(
+    harness: &mut Harness,
+    authority: &Keypair,
+    event_index: u32,
+) -> Pubkey {
+    let organizer = find_organizer_address(&authority.pubkey()).0;
+    let accepted_mint = harness.accepted_mint;
+
+    harness
+        .process(
+            &[instructions::create_organizer_event(
+                authority.pubkey(),
+                event_index,
+                accepted_mint,
+                format!("Tomorrowland {}", 2022 + event_index),
+            )],
+            &[authority],
+        )
+        .await
+        .unwrap();
+
+    event(&find_organizer_event_base_address(&organizer, event_index).0)
+}
+
+async fn create_series(harness: &mut Harness, authority: &Keypair) -> Pubkey {
+    let series_base = Keypair::new().pubkey();
+    let organizer = find_organizer_address(&authority.pubkey()).0;
+
+    harness
+        .process(
+            &[instructions::create_series(
+                authority.pubkey(),
+                series_base,
+                "Tomorrowland".to_string(),
+            )],
+            &[authority],
+        )
+        .await
+        .unwrap();
+
+    find_series_address(&organizer, &series_base).0
+}
+
+#[tokio::test]
+async fn creates_and_updates_organizer() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+    let organizer = create_organizer(&mut harness, &authority).await;
+
+    harness
+        .process(
+            &[instructions::update_organizer(
+                authority.pubkey(),
+                "Tomorrowland Winter".to_string(),
+                "https://tomorrowland.com/winter".to_string(),
+            )],
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let organizer: Organizer = harness.fetch(organizer).await;
+    assert_eq!(organizer.authority, authority.pubkey());
+    assert_eq!(organizer.name, "Tomorrowland Winter");
+    assert_eq!(organizer.uri, "https://tomorrowland.com/winter");
+    assert!(!organizer.verified);
+    assert_eq!(organizer.event_count, 0);
+}
+
+#[tokio::test]
+async fn rejects_long_organizer_fields() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::create_organizer(
+                authority.pubkey(),
+                "a".repeat(33),
+                "https://tomorrowland.com".to_string(),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NameTooLong);
+
+    let result = harness
+        .process(
+            &[instructions::create_organizer(
+                authority.pubkey(),
+                "Tomorrowland".to_string(),
+                "a".repeat(201),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::UriTooLong);
+
+    create_organizer(&mut harness, &authority).await;
+
+    let result = harness
+        .process(
+            &[instructions::update_organizer(
+                authority.pubkey(),
+                "a".repeat(33),
+                "https://tomorrowland.com".to_string(),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NameTooLong);
+
+    let result = harness
+        .process(
+            &[instructions::update_organizer(
+                authority.pubkey(),
+                "Tomorrowland".to_string(),
+                "a".repeat(201),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::UriTooLong);
+
+    let result = harness
+        .process(
+            &[instructions::create_series(
+                authority.pubkey(),
+                Keypair::new().pubkey(),
+                "a".repeat(33),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NameTooLong);
+}
+
+#[tokio::test]
+async fn rejects_invalid_organizer_names() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+
+    for name in ["", "   ", "Tomorrow\nland"] {
+        let result = harness
+            .process(
+                &[instructions::create_organizer(
+                    authority.pubkey(),
+                    name.to_string(),
+                    "https://tomorrowland.com".to_string(),
+                )],
+                &[&authority],
+            )
+            .await;
+        assert_error(result, ErrorCode::InvalidName);
+    }
+
+    create_organizer(&mut harness, &authority).await;
+
+    for name in ["", "   ", "Tomorrow\tland"] {
+        let result = harness
+            .process(
+                &[instructions::update_organizer(
+                    authority.pubkey(),
+                    name.to_string(),
+                    "https://tomorrowland.com".to_string(),
+                )],
+                &[&authority],
+            )
+            .await;
+        assert_error(result, ErrorCode::InvalidName);
+
+        let result = harness
+            .process(
+                &[instructions::create_series(
+                    authority.pubkey(),
+                    Keypair::new().pubkey(),
+                    name.to_string(),
+                )],
+                &[&authority],
+            )
+            .await;
+        assert_error(result, ErrorCode::InvalidName);
+    }
+}
+
+#[tokio::test]
+async fn creates_organizer_events_in_sequence() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+    let organizer = create_organizer(&mut harness, &authority).await;
+
+    let first = create_organizer_event(&mut harness, &authority, 0).await;
+    let second = create_organizer_event(&mut harness, &authority, 1).await;
+
+    let first: Event = harness.fetch(first).await;
+    let second: Event = harness.fetch(second).await;
+    assert_eq!(first.organizer, Some(organizer));
+    assert_eq!(second.event_title, "Tomorrowland 2023");
+
+    let organizer: Organizer = harness.fetch(organizer).await;
+    assert_eq!(organizer.event_count, 2);
+}
+
+#[tokio::test]
+async fn adds_events_to_series() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+    create_organizer(&mut harness, &authority).await;
+    let series = create_series(&mut harness, &authority).await;
+    let event = create_organizer_event(&mut harness, &authority, 0).await;
+
+    harness
+        .process(
+            &[instructions::add_event_to_series(
+                authority.pubkey(),
+                series,
+                event,
+            )],
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let event_account: Event = harness.fetch(event).await;
+    assert_eq!(event_account.series, Some(series));
+
+    let series_account: Series = harness.fetch(series).await;
+    assert_eq!(series_account.event_count, 1);
+
+    let result = harness
+        .process(
+            &[instructions::add_event_to_series(
+                authority.pubkey(),
+                series,
+                event,
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventAlreadyInSeries);
+}
+
+#[tokio::test]
+async fn rejects_events_and_series_of_other_organizers() {
+    let mut harness = Harness::start().await;
+    let authority = harness.create_user().await;
+    let other_authority = harness.create_user().await;
+    create_organizer(&mut harness, &authority).await;
+    create_organizer(&mut harness, &other_authority).await;
+    let series = create_series(&mut harness, &authority).await;
+    let other_series = create_series(&mut harness, &other_authority).await;
+    let other_event = create_organizer_event(&mut harness, &other_authority, 0).await;
+
+    let result = harness
+        .process(
+            &[instructions::add_event_to_series(
+                authority.pubkey(),
+                other_series,
+                other_event,
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::SeriesBelongsToAnotherOrganizer);
+
+    let result = harness
+        .process(
+            &[instructions::add_event_to_series(
+                authority.pubkey(),
+                series,
+                other_event,
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanAddToSeries);
+
+    let event_base = Keypair::new().pubkey();
+    let accepted_mint = harness.accepted_mint;
+    harness
+        .process(
+            &[instructions::create_event(
+                authority.pubkey(),
+                event_base,
+                accepted_mint,
+                "Tomorrowland 2022".to_string(),
+            )],
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::add_event_to_series(
+                authority.pubkey(),
+                series,
+                event(&event_base),
+            )],
+            &[&authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::EventBelongsToAnotherOrganizer);
+}
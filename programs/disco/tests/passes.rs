@@ -0,0 +1,337 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{Entry, EventTicket, PassUsage},
+    instructions,
+    pda::{find_entry_address, find_pass_usage_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn checks_in_passes_per_session() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_sessions(
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                2,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetSessions);
+
+    harness
+        .process(
+            &[instructions::set_ticket_sessions(
+                authority,
+                event_base,
+                event_ticket_base,
+                2,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            1,
+        )
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_sessions(
+                authority,
+                event_base,
+                event_ticket_base,
+                3,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SessionsCanOnlyBeSetBeforeSales);
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsAPass);
+
+    let check_in_pass = |session: u8, ticket_quantity: u32| {
+        instructions::check_in_pass(
+            authority,
+            authority,
+            attendee.pubkey(),
+            event_base,
+            event_ticket_base,
+            ticket_vault,
+            session,
+            ticket_quantity,
+        )
+    };
+
+    for (session, ticket_quantity, error) in [
+        (2, 1, ErrorCode::InvalidSession),
+        (0, 2, ErrorCode::NotEnoughTicketsToCheckIn),
+    ] {
+        let result = harness
+            .process(&[check_in_pass(session, ticket_quantity)], &[&attendee])
+            .await;
+        assert_error(result, error);
+    }
+
+    // the pass is frozen after its first check-in so it can't be passed on
+    harness
+        .process(&[check_in_pass(0, 1)], &[&attendee])
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+    assert!(harness.is_frozen(ticket_vault).await);
+
+    let result = harness.process(&[check_in_pass(0, 1)], &[&attendee]).await;
+    assert_error(result, ErrorCode::NotEnoughTicketsToCheckIn);
+
+    // and burned once it's checked in to the last session
+    harness
+        .process(&[check_in_pass(1, 1)], &[&attendee])
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(ticket_vault).await, 0);
+    assert!(!harness.is_frozen(ticket_vault).await);
+
+    let result = harness.process(&[check_in_pass(1, 1)], &[&attendee]).await;
+    assert_error(result, ErrorCode::NotEnoughTicketsToCheckIn);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let pass_usage: PassUsage = harness
+        .fetch(find_pass_usage_address(&event_ticket_address, &attendee.pubkey()).0)
+        .await;
+    assert_eq!(pass_usage.passes, 1);
+    assert_eq!(pass_usage.sessions_used, vec![1, 1]);
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.used, 1);
+
+    let result = harness
+        .process(
+            &[instructions::create_proof_of_attendance(
+                authority,
+                event_base,
+                event_ticket_base,
+                "Tomorrowland 2022 - POAP".to_string(),
+                "TMRWP".to_string(),
+                "https://tomorrowland.com/poap.json".to_string(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::PassesCantHaveProofOfAttendance);
+}
+
+#[tokio::test]
+async fn rejects_pass_check_ins_for_single_entry_tickets() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            1,
+        )
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::check_in_pass(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                0,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsNotAPass);
+}
+
+#[tokio::test]
+async fn lets_attendees_leave_and_re_enter() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            1,
+        )
+        .await;
+
+    let check_in_with_reentry = instructions::check_in_with_reentry(
+        authority,
+        authority,
+        attendee.pubkey(),
+        event_base,
+        event_ticket_base,
+        ticket_vault,
+        1,
+    );
+    let check_out =
+        instructions::check_out(authority, attendee.pubkey(), event_base, event_ticket_base);
+    let re_enter =
+        instructions::re_enter(authority, attendee.pubkey(), event_base, event_ticket_base);
+
+    let result = harness
+        .process(std::slice::from_ref(&check_in_with_reentry), &[&attendee])
+        .await;
+    assert_error(result, ErrorCode::TicketDoesNotAllowReentry);
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_reentry(
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                true,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetReentry);
+
+    harness
+        .process(
+            &[instructions::set_ticket_reentry(
+                authority,
+                event_base,
+                event_ticket_base,
+                true,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketAllowsReentry);
+
+    harness
+        .process(&[check_in_with_reentry], &[&attendee])
+        .await
+        .unwrap();
+
+    // the tickets are frozen on the way in so they can't be handed out
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+    assert!(harness.is_frozen(ticket_vault).await);
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_reentry(
+                authority,
+                event_base,
+                event_ticket_base,
+                false,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::ReentryCanOnlyBeSetBeforeCheckIns);
+
+    let result = harness.process(std::slice::from_ref(&re_enter), &[]).await;
+    assert_error(result, ErrorCode::AttendeeIsAlreadyInside);
+
+    harness
+        .process(std::slice::from_ref(&check_out), &[])
+        .await
+        .unwrap();
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let entry_address = find_entry_address(&event_ticket_address, &attendee.pubkey()).0;
+    let entry: Entry = harness.fetch(entry_address).await;
+    assert!(!entry.inside);
+
+    let result = harness.process(&[check_out], &[]).await;
+    assert_error(result, ErrorCode::AttendeeIsNotInside);
+
+    harness.process(&[re_enter], &[]).await.unwrap();
+
+    harness
+        .process(
+            &[instructions::finalize_entry(
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+            )],
+            &[&attendee],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.used, 1);
+    assert_eq!(harness.token_balance(ticket_vault).await, 0);
+    assert!(!harness.is_frozen(ticket_vault).await);
+    assert!(harness.account(entry_address).await.is_none());
+}
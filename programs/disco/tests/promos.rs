@@ -0,0 +1,406 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::{DiscountKind, ErrorCode};
+use disco_client::{
+    accounts::{Affiliate, Promo},
+    instructions,
+    pda::{find_affiliate_address, find_promo_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+struct Sale {
+    event_base: Pubkey,
+    event_ticket_base: Pubkey,
+    buyer: Keypair,
+    buyer_vault: Pubkey,
+    ticket_vault: Pubkey,
+}
+
+async fn start_sale(harness: &mut Harness) -> Sale {
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 10, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &buyer.pubkey())
+        .await;
+
+    Sale {
+        event_base,
+        event_ticket_base,
+        buyer,
+        buyer_vault,
+        ticket_vault,
+    }
+}
+
+// Promos created here can be redeemed for two tickets.
+async fn create_promo(
+    harness: &mut Harness,
+    sale: &Sale,
+    code: &str,
+    discount_kind: DiscountKind,
+    discount_value: u32,
+    event_ticket: Option<Pubkey>,
+    expires_at: Option<i64>,
+) -> [u8; 32] {
+    let code_hash = hash(code.as_bytes()).to_bytes();
+    let authority = harness.payer();
+
+    harness
+        .process(
+            &[instructions::create_promo(
+                authority,
+                sale.event_base,
+                code_hash,
+                discount_kind,
+                discount_value,
+                2,
+                event_ticket,
+                expires_at,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    code_hash
+}
+
+fn buy_with_promo_instruction(
+    harness: &Harness,
+    sale: &Sale,
+    promo_code: &str,
+    ticket_quantity: u32,
+) -> Instruction {
+    instructions::buy_tickets_with_promo(
+        sale.buyer.pubkey(),
+        sale.buyer.pubkey(),
+        sale.buyer.pubkey(),
+        sale.event_base,
+        sale.event_ticket_base,
+        sale.buyer_vault,
+        harness.fee_vault,
+        promo_code.to_string(),
+        ticket_quantity,
+    )
+}
+
+async fn buy_with_promo(
+    harness: &mut Harness,
+    sale: &Sale,
+    promo_code: &str,
+    ticket_quantity: u32,
+) -> solana_sdk::transport::Result<()> {
+    let instruction = buy_with_promo_instruction(harness, sale, promo_code, ticket_quantity);
+
+    harness.process(&[instruction], &[&sale.buyer]).await
+}
+
+#[tokio::test]
+async fn buys_tickets_with_promos() {
+    let mut harness = Harness::start().await;
+    let sale = start_sale(&mut harness).await;
+    let percentage = create_promo(
+        &mut harness,
+        &sale,
+        "EARLYBIRD",
+        DiscountKind::Percentage,
+        20,
+        None,
+        None,
+    )
+    .await;
+    let fixed = create_promo(
+        &mut harness,
+        &sale,
+        "FRIENDS",
+        DiscountKind::Fixed,
+        3,
+        None,
+        None,
+    )
+    .await;
+
+    buy_with_promo(&mut harness, &sale, "EARLYBIRD", 2)
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(sale.buyer_vault).await, 84);
+
+    // redemptions are counted per ticket
+    let result = buy_with_promo(&mut harness, &sale, "FRIENDS", 3).await;
+    assert_error(result, ErrorCode::PromoHasNoRedemptionsLeft);
+
+    buy_with_promo(&mut harness, &sale, "FRIENDS", 2)
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(sale.buyer_vault).await, 70);
+    assert_eq!(harness.token_balance(sale.ticket_vault).await, 4);
+
+    let promo: Promo = harness
+        .fetch(find_promo_address(&event(&sale.event_base), &percentage).0)
+        .await;
+    assert_eq!(promo.redemptions, 2);
+
+    let result = buy_with_promo(&mut harness, &sale, "EARLYBIRD", 1).await;
+    assert_error(result, ErrorCode::PromoHasNoRedemptionsLeft);
+
+    // knowing a promo's address isn't enough, the code itself has to be passed
+    let mut instruction = buy_with_promo_instruction(&harness, &sale, "GUESSED", 1);
+    *instruction.accounts.last_mut().unwrap() = AccountMeta::new(
+        find_promo_address(&event(&sale.event_base), &fixed).0,
+        false,
+    );
+    let result = harness.process(&[instruction], &[&sale.buyer]).await;
+    assert_error(result, ErrorCode::InvalidPromoCode);
+}
+
+#[tokio::test]
+async fn rejects_invalid_promos() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let sale = start_sale(&mut harness).await;
+
+    let result = harness
+        .process(
+            &[instructions::create_promo(
+                authority,
+                sale.event_base,
+                hash(b"TOOGOOD").to_bytes(),
+                DiscountKind::Percentage,
+                101,
+                1,
+                None,
+                None,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPromoDiscount);
+
+    let result = harness
+        .process(
+            &[instructions::create_promo(
+                sale.buyer.pubkey(),
+                sale.event_base,
+                hash(b"MINE").to_bytes(),
+                DiscountKind::Fixed,
+                10,
+                1,
+                None,
+                None,
+            )],
+            &[&sale.buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanCreatePromos);
+
+    let other_ticket = Keypair::new().pubkey();
+    create_promo(
+        &mut harness,
+        &sale,
+        "VIPONLY",
+        DiscountKind::Fixed,
+        1,
+        Some(other_ticket),
+        None,
+    )
+    .await;
+    let result = buy_with_promo(&mut harness, &sale, "VIPONLY", 1).await;
+    assert_error(result, ErrorCode::PromoIsNotValidForThisTicket);
+
+    let now = harness.now().await;
+    create_promo(
+        &mut harness,
+        &sale,
+        "LASTYEAR",
+        DiscountKind::Fixed,
+        1,
+        None,
+        Some(now - 1),
+    )
+    .await;
+    let result = buy_with_promo(&mut harness, &sale, "LASTYEAR", 1).await;
+    assert_error(result, ErrorCode::PromoHasExpired);
+}
+
+#[tokio::test]
+async fn deletes_promos() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let sale = start_sale(&mut harness).await;
+    let code_hash = create_promo(
+        &mut harness,
+        &sale,
+        "EARLYBIRD",
+        DiscountKind::Percentage,
+        20,
+        None,
+        None,
+    )
+    .await;
+
+    let result = harness
+        .process(
+            &[instructions::delete_promo(
+                sale.buyer.pubkey(),
+                sale.event_base,
+                code_hash,
+            )],
+            &[&sale.buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanDeletePromos);
+
+    harness
+        .process(
+            &[instructions::delete_promo(
+                authority,
+                sale.event_base,
+                code_hash,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let promo = find_promo_address(&event(&sale.event_base), &code_hash).0;
+    assert!(harness.account(promo).await.is_none());
+}
+
+#[tokio::test]
+async fn pays_affiliate_commissions() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let sale = start_sale(&mut harness).await;
+    let affiliate_base = harness.create_user().await;
+    let affiliate = find_affiliate_address(&event(&sale.event_base), &affiliate_base.pubkey()).0;
+    let affiliate_vault = harness.create_wallet(&affiliate_base.pubkey(), 0).await;
+
+    let result = harness
+        .process(
+            &[instructions::create_affiliate(
+                authority,
+                sale.event_base,
+                affiliate_base.pubkey(),
+                10_001,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidCommissionRate);
+
+    let result = harness
+        .process(
+            &[instructions::create_affiliate(
+                sale.buyer.pubkey(),
+                sale.event_base,
+                affiliate_base.pubkey(),
+                1_000,
+            )],
+            &[&sale.buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanCreateAffiliates);
+
+    harness
+        .process(
+            &[instructions::create_affiliate(
+                authority,
+                sale.event_base,
+                affiliate_base.pubkey(),
+                1_000,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let fee_vault = harness.fee_vault;
+    let buy_with_affiliate = |affiliate_vault: Pubkey| {
+        instructions::buy_tickets_with_affiliate(
+            sale.buyer.pubkey(),
+            sale.buyer.pubkey(),
+            sale.buyer.pubkey(),
+            sale.event_base,
+            sale.event_ticket_base,
+            sale.buyer_vault,
+            fee_vault,
+            affiliate_base.pubkey(),
+            affiliate_vault,
+            3,
+        )
+    };
+
+    let instruction = buy_with_affiliate(sale.buyer_vault);
+    let result = harness.process(&[instruction], &[&sale.buyer]).await;
+    assert_error(result, ErrorCode::InvalidAffiliateVault);
+
+    // the affiliate account has to be the one of the named affiliate
+    let mut instruction = instructions::buy_tickets_with_affiliate(
+        sale.buyer.pubkey(),
+        sale.buyer.pubkey(),
+        sale.buyer.pubkey(),
+        sale.event_base,
+        sale.event_ticket_base,
+        sale.buyer_vault,
+        fee_vault,
+        sale.buyer.pubkey(),
+        affiliate_vault,
+        3,
+    );
+    let affiliate_index = instruction.accounts.len() - 2;
+    instruction.accounts[affiliate_index].pubkey = affiliate;
+    let result = harness.process(&[instruction], &[&sale.buyer]).await;
+    assert_error(result, ErrorCode::InvalidAffiliate);
+
+    let instruction = buy_with_affiliate(affiliate_vault);
+    harness
+        .process(&[instruction], &[&sale.buyer])
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(affiliate_vault).await, 3);
+    assert_eq!(
+        harness.token_balance(event_vault(&sale.event_base)).await,
+        27
+    );
+
+    let affiliate_account: Affiliate = harness.fetch(affiliate).await;
+    assert_eq!(affiliate_account.tickets_sold, 3);
+    assert_eq!(affiliate_account.commission_earned, 3);
+
+    let result = harness
+        .process(
+            &[instructions::delete_affiliate(
+                sale.buyer.pubkey(),
+                sale.event_base,
+                affiliate_base.pubkey(),
+            )],
+            &[&sale.buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanDeleteAffiliates);
+
+    harness
+        .process(
+            &[instructions::delete_affiliate(
+                authority,
+                sale.event_base,
+                affiliate_base.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(harness.account(affiliate).await.is_none());
+}
@@ -0,0 +1,497 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{EventTicket, Raffle, WalletPurchase},
+    instructions,
+    pda::{find_raffle_address, find_wallet_purchase_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{hash::hash, pubkey::Pubkey, signature::Signer};
+
+const SECRET: [u8; 32] = [7; 32];
+
+async fn create_raffle(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    registration_start: i64,
+    registration_end: i64,
+) -> solana_sdk::transport::Result<()> {
+    let authority = harness.payer();
+    let accepted_mint = harness.accepted_mint;
+
+    harness
+        .process(
+            &[instructions::create_raffle(
+                authority,
+                *event_base,
+                *event_ticket_base,
+                accepted_mint,
+                registration_start,
+                registration_end,
+                hash(&SECRET).to_bytes(),
+            )],
+            &[],
+        )
+        .await
+}
+
+#[tokio::test]
+async fn draws_raffle_and_refunds_losers() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    let now = harness.now().await;
+    let registration_end = now + 100;
+
+    create_raffle(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        now,
+        registration_end,
+    )
+    .await
+    .unwrap();
+
+    let mut entrants = Vec::new();
+    for _ in 0..2 {
+        let entrant = harness.create_user().await;
+        let buyer_vault = harness.create_wallet(&entrant.pubkey(), 100).await;
+        let ticket_vault = harness
+            .create_ticket_vault(&event_base, &event_ticket_base, &entrant.pubkey())
+            .await;
+
+        harness
+            .process(
+                &[instructions::enter_raffle(
+                    entrant.pubkey(),
+                    entrant.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    buyer_vault,
+                )],
+                &[&entrant],
+            )
+            .await
+            .unwrap();
+
+        entrants.push((entrant, buyer_vault, ticket_vault));
+    }
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let raffle_address = find_raffle_address(&event_ticket_address).0;
+    let raffle: Raffle = harness.fetch(raffle_address).await;
+    assert_eq!(raffle.entries, 2);
+
+    let result = harness
+        .process(
+            &[instructions::seal_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleRegistrationIsStillOpen);
+
+    let result = harness
+        .process(
+            &[instructions::draw_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+                harness.fee_vault,
+                SECRET,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleIsNotSealed);
+
+    harness.set_time(registration_end + 1).await;
+
+    let latecomer = harness.create_user().await;
+    let latecomer_vault = harness.create_wallet(&latecomer.pubkey(), 100).await;
+    let result = harness
+        .process(
+            &[instructions::enter_raffle(
+                latecomer.pubkey(),
+                latecomer.pubkey(),
+                event_base,
+                event_ticket_base,
+                latecomer_vault,
+            )],
+            &[&latecomer],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleRegistrationIsClosed);
+
+    let (entrant, buyer_vault, _) = &entrants[0];
+    let result = harness
+        .process(
+            &[instructions::claim_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                *buyer_vault,
+            )],
+            &[entrant],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleIsNotDrawn);
+
+    harness
+        .process(
+            &[instructions::seal_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::seal_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleIsAlreadySealed);
+
+    // the hash of the seal slot is only known once the slot is over
+    let result = harness
+        .process(
+            &[instructions::draw_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+                harness.fee_vault,
+                SECRET,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleSealIsNotAvailable);
+
+    harness.advance_slot().await;
+
+    let result = harness
+        .process(
+            &[instructions::draw_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+                harness.fee_vault,
+                [0; 32],
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidRaffleSecret);
+
+    harness
+        .process(
+            &[instructions::draw_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+                harness.fee_vault,
+                SECRET,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let raffle: Raffle = harness.fetch(raffle_address).await;
+    assert!(raffle.drawn);
+    assert_eq!(raffle.winners, 1);
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.sold, 1);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 5);
+
+    let result = harness
+        .process(
+            &[instructions::draw_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+                harness.fee_vault,
+                SECRET,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleIsAlreadyDrawn);
+
+    let mut tickets = 0;
+    let mut balances = 0;
+    let mut purchased = 0;
+    for (entrant, buyer_vault, ticket_vault) in &entrants {
+        harness
+            .process(
+                &[instructions::claim_raffle_entry(
+                    entrant.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    *buyer_vault,
+                )],
+                &[entrant],
+            )
+            .await
+            .unwrap();
+
+        tickets += harness.token_balance(*ticket_vault).await;
+        balances += harness.token_balance(*buyer_vault).await;
+
+        // losing entries stop counting against the wallet limit
+        let wallet_purchase: WalletPurchase = harness
+            .fetch(find_wallet_purchase_address(&event_ticket_address, &entrant.pubkey()).0)
+            .await;
+        purchased += wallet_purchase.quantity;
+    }
+    assert_eq!(tickets, 1);
+    assert_eq!(balances, 195);
+    assert_eq!(purchased, 1);
+
+    let (entrant, buyer_vault, _) = &entrants[0];
+    let result = harness
+        .process(
+            &[instructions::claim_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                *buyer_vault,
+            )],
+            &[entrant],
+        )
+        .await;
+    assert_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn refunds_raffle_entries_after_draw_deadline() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    let entrant = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&entrant.pubkey(), 100).await;
+    let other_vault = harness.create_wallet(&authority, 0).await;
+    let now = harness.now().await;
+    let registration_end = now + 100;
+
+    create_raffle(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        now,
+        registration_end,
+    )
+    .await
+    .unwrap();
+
+    harness
+        .process(
+            &[instructions::enter_raffle(
+                entrant.pubkey(),
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[&entrant],
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(buyer_vault).await, 95);
+
+    harness.set_time(registration_end + 1).await;
+
+    let result = harness
+        .process(
+            &[instructions::refund_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleDrawDeadlineHasNotPassed);
+
+    harness
+        .set_time(registration_end + Raffle::DRAW_WINDOW + 1)
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::seal_raffle(
+                authority,
+                event_base,
+                event_ticket_base,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::RaffleDrawDeadlineHasPassed);
+
+    let result = harness
+        .process(
+            &[instructions::refund_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                other_vault,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidRefundVault);
+
+    // anyone can push the refund through, it always goes to the entrant
+    harness
+        .process(
+            &[instructions::refund_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(buyer_vault).await, 100);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let wallet_purchase: WalletPurchase = harness
+        .fetch(find_wallet_purchase_address(&event_ticket_address, &entrant.pubkey()).0)
+        .await;
+    assert_eq!(wallet_purchase.quantity, 0);
+
+    let result = harness
+        .process(
+            &[instructions::refund_raffle_entry(
+                entrant.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn rejects_invalid_raffles() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let now = harness.now().await;
+
+    let result = create_raffle(&mut harness, &event_base, &event_ticket_base, now, now).await;
+    assert_error(result, ErrorCode::InvalidRaffleSchedule);
+
+    let accepted_mint = harness.accepted_mint;
+    let result = harness
+        .process(
+            &[instructions::create_raffle(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                accepted_mint,
+                now,
+                now + 100,
+                hash(&SECRET).to_bytes(),
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanManageRaffles);
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+
+    let result = create_raffle(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        now,
+        now + 100,
+    )
+    .await;
+    assert_error(result, ErrorCode::RaffleCanOnlyBeCreatedBeforeSales);
+}
+
+#[tokio::test]
+async fn keeps_raffle_tickets_out_of_direct_sales() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let now = harness.now().await;
+
+    create_raffle(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        now,
+        now + 100,
+    )
+    .await
+    .unwrap();
+
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                1,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsSoldThroughRaffle);
+
+    let result = harness
+        .process(
+            &[instructions::reserve_tickets(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                1,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketIsSoldThroughRaffle);
+}
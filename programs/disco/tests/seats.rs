@@ -0,0 +1,350 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{EventTicket, SeatMap, SeatReservation},
+    instructions,
+    pda::{find_seat_map_address, find_seat_reservation_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+async fn create_seat_map(harness: &mut Harness, event_base: &Pubkey, event_ticket_base: &Pubkey) {
+    let authority = harness.payer();
+
+    harness
+        .process(
+            &[instructions::create_seat_map(
+                authority,
+                *event_base,
+                *event_ticket_base,
+                1,
+                2,
+                3,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+}
+
+async fn buy_seats(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    buyer: &Keypair,
+    buyer_vault: &Pubkey,
+    seat_ids: Vec<u32>,
+) -> (Pubkey, solana_sdk::transport::Result<()>) {
+    let seat_reservation_base = Keypair::new().pubkey();
+    let result = harness
+        .process(
+            &[instructions::buy_seats(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                *buyer_vault,
+                harness.fee_vault,
+                seat_reservation_base,
+                seat_ids,
+            )],
+            &[buyer],
+        )
+        .await;
+
+    (seat_reservation_base, result)
+}
+
+#[tokio::test]
+async fn buys_seats() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let stranger = harness.create_user().await;
+
+    let result = harness
+        .process(
+            &[instructions::create_seat_map(
+                stranger.pubkey(),
+                event_base,
+                event_ticket_base,
+                1,
+                2,
+                3,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanCreateSeatMaps);
+
+    let authority = harness.payer();
+    let result = harness
+        .process(
+            &[instructions::create_seat_map(
+                authority,
+                event_base,
+                event_ticket_base,
+                10,
+                100,
+                100,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SeatMapTooLarge);
+
+    create_seat_map(&mut harness, &event_base, &event_ticket_base).await;
+
+    let result = harness
+        .process(
+            &[instructions::create_proof_of_attendance(
+                authority,
+                event_base,
+                event_ticket_base,
+                "Tomorrowland 2022 - POAP".to_string(),
+                "TMRWP".to_string(),
+                "https://tomorrowland.com/poap.json".to_string(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SeatMapsCantHaveProofOfAttendance);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let seat_map: SeatMap = harness
+        .fetch(find_seat_map_address(&event_ticket_address).0)
+        .await;
+    assert_eq!(seat_map.capacity(), 6);
+    assert_eq!(seat_map.seat_id(0, 1, 2), 5);
+
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &buyer.pubkey())
+        .await;
+
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                1,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketHasSeatMap);
+
+    for (seat_ids, error) in [
+        (vec![], ErrorCode::InvalidSeatQuantity),
+        ((0..11).collect(), ErrorCode::InvalidSeatQuantity),
+        (vec![6], ErrorCode::InvalidSeat),
+        (vec![1, 1], ErrorCode::SeatIsAlreadyTaken),
+    ] {
+        let (_, result) = buy_seats(
+            &mut harness,
+            &event_base,
+            &event_ticket_base,
+            &buyer,
+            &buyer_vault,
+            seat_ids,
+        )
+        .await;
+        assert_error(result, error);
+    }
+
+    let (seat_reservation_base, result) = buy_seats(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        vec![0, 5],
+    )
+    .await;
+    result.unwrap();
+
+    assert_eq!(harness.token_balance(ticket_vault).await, 2);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 10);
+
+    let seat_reservation: SeatReservation = harness
+        .fetch(find_seat_reservation_address(&event_ticket_address, &seat_reservation_base).0)
+        .await;
+    assert_eq!(seat_reservation.owner, buyer.pubkey());
+    assert_eq!(seat_reservation.seats.len(), 2);
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.sold, 2);
+
+    let (_, result) = buy_seats(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        vec![5],
+    )
+    .await;
+    assert_error(result, ErrorCode::SeatIsAlreadyTaken);
+
+    harness
+        .process(
+            &[instructions::set_ticket_wallet_limit(
+                authority,
+                event_base,
+                event_ticket_base,
+                2,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    let (_, result) = buy_seats(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &buyer,
+        &buyer_vault,
+        vec![1],
+    )
+    .await;
+    assert_error(result, ErrorCode::WalletLimitExceeded);
+
+    let other_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness
+        .buy_tickets(&event_base, &other_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::create_seat_map(
+                authority,
+                event_base,
+                other_ticket_base,
+                1,
+                2,
+                3,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SeatMapCanOnlyBeCreatedBeforeSales);
+}
+
+#[tokio::test]
+async fn checks_in_seats() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    create_seat_map(&mut harness, &event_base, &event_ticket_base).await;
+    harness.add_self_as_collaborator(&event_base).await;
+
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &attendee.pubkey())
+        .await;
+    let (seat_reservation_base, result) = buy_seats(
+        &mut harness,
+        &event_base,
+        &event_ticket_base,
+        &attendee,
+        &attendee_vault,
+        vec![0, 1],
+    )
+    .await;
+    result.unwrap();
+
+    let check_in_seat = |attendee: &Keypair, ticket_vault: Pubkey, seat_id: u32| {
+        instructions::check_in_seat(
+            authority,
+            authority,
+            attendee.pubkey(),
+            event_base,
+            event_ticket_base,
+            ticket_vault,
+            seat_reservation_base,
+            seat_id,
+        )
+    };
+
+    let stranger = harness.create_user().await;
+    let stranger_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &stranger.pubkey())
+        .await;
+    let result = harness
+        .process(&[check_in_seat(&stranger, stranger_vault, 0)], &[&stranger])
+        .await;
+    assert_error(result, ErrorCode::OnlySeatOwnerCanCheckIn);
+
+    let result = harness
+        .process(&[check_in_seat(&attendee, ticket_vault, 2)], &[&attendee])
+        .await;
+    assert_error(result, ErrorCode::SeatIsNotInReservation);
+
+    harness
+        .process(&[check_in_seat(&attendee, ticket_vault, 0)], &[&attendee])
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(&[check_in_seat(&attendee, ticket_vault, 0)], &[&attendee])
+        .await;
+    assert_error(result, ErrorCode::SeatIsAlreadyCheckedIn);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let seat_reservation: SeatReservation = harness
+        .fetch(find_seat_reservation_address(&event_ticket_address, &seat_reservation_base).0)
+        .await;
+    assert!(seat_reservation.seats[0].checked_in);
+    assert!(!seat_reservation.seats[1].checked_in);
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.used, 1);
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+
+    // seated tickets are checked in by seat, so the seat map stays accurate
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketHasSeatMap);
+
+    let result = harness
+        .process(
+            &[instructions::issue_comp_tickets(
+                authority,
+                event_base,
+                event_ticket_base,
+                stranger.pubkey(),
+                1,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketHasSeatMap);
+}
@@ -0,0 +1,994 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::{ErrorCode, PricingCurve};
+use disco_client::{
+    accounts::{Event, EventTicket, WalletPurchase},
+    instructions,
+    pda::find_wallet_purchase_address,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+#[tokio::test]
+async fn creates_event_ticket() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.price, 5);
+    assert_eq!(event_ticket.quantity, 10);
+    assert_eq!(event_ticket.sold, 0);
+    assert!(!event_ticket.has_poap);
+
+    let event: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(event.total_allocated, 10);
+
+    let metadata =
+        disco_client::pda::find_metadata_address(&ticket_mint(&event_base, &event_ticket_base)).0;
+    let metadata = harness.account(metadata).await.unwrap();
+    assert_eq!(metadata.owner, mpl_token_metadata::ID);
+}
+
+#[tokio::test]
+async fn buys_tickets() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+
+    let ticket_vault = harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 2)
+        .await;
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.sold, 2);
+
+    let event: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(event.total_sold, 2);
+
+    assert_eq!(harness.token_balance(ticket_vault).await, 2);
+    assert_eq!(harness.token_balance(buyer_vault).await, 90);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 10);
+
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                9,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotEnoughTicketsAvailable);
+}
+
+#[tokio::test]
+async fn prices_tickets_along_a_curve() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Stepped {
+                    step_size: 0,
+                    increment: 1,
+                }),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPricingCurve);
+
+    let result = harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Linear { increment: 1 }),
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetPricingCurves);
+
+    harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Linear { increment: 1 }),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 3)
+        .await;
+
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 18);
+
+    harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Stepped {
+                    step_size: 2,
+                    increment: 3,
+                }),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 4)
+        .await;
+
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 62);
+}
+
+#[tokio::test]
+async fn prices_large_purchases_along_a_curve() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 1, 60_000).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 2_000_000_000).await;
+
+    harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Linear { increment: 1 }),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &buyer,
+            &buyer_vault,
+            50_000,
+        )
+        .await;
+
+    assert_eq!(
+        harness.token_balance(event_vault(&event_base)).await,
+        50_000 + 50_000 * 49_999 / 2
+    );
+}
+
+#[tokio::test]
+async fn issues_comp_tickets() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let collaborator = harness.create_user().await;
+    let stranger = harness.create_user().await;
+    // the recipient's ticket account is created along with the comps
+    let recipient_vault = get_associated_token_address(
+        &stranger.pubkey(),
+        &ticket_mint(&event_base, &event_ticket_base),
+    );
+
+    let result = harness
+        .process(
+            &[instructions::issue_comp_tickets(
+                stranger.pubkey(),
+                event_base,
+                event_ticket_base,
+                stranger.pubkey(),
+                1,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(
+        result,
+        ErrorCode::OnlyEventAuthorityOrCollaboratorsCanIssueCompTickets,
+    );
+
+    harness
+        .process(
+            &[instructions::create_collaborator(
+                authority,
+                event_base,
+                collaborator.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(
+            &[
+                instructions::issue_comp_tickets(
+                    authority,
+                    event_base,
+                    event_ticket_base,
+                    stranger.pubkey(),
+                    2,
+                ),
+                instructions::issue_comp_tickets(
+                    collaborator.pubkey(),
+                    event_base,
+                    event_ticket_base,
+                    stranger.pubkey(),
+                    3,
+                ),
+            ],
+            &[&collaborator],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.comps, 5);
+    assert_eq!(harness.token_balance(recipient_vault).await, 5);
+
+    let result = harness
+        .process(
+            &[instructions::issue_comp_tickets(
+                authority,
+                event_base,
+                event_ticket_base,
+                stranger.pubkey(),
+                6,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotEnoughTicketsAvailable);
+
+    // comps count as sales for settings that can only change before them
+    let result = harness
+        .process(
+            &[instructions::create_seat_map(
+                authority,
+                event_base,
+                event_ticket_base,
+                1,
+                1,
+                10,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SeatMapCanOnlyBeCreatedBeforeSales);
+
+    let start_time = harness.now().await;
+    let result = harness
+        .process(
+            &[instructions::set_ticket_auction(
+                authority,
+                event_base,
+                event_ticket_base,
+                10,
+                5,
+                start_time,
+                60,
+                1,
+                false,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::AuctionCanOnlyBeSetBeforeSales);
+}
+
+#[tokio::test]
+async fn checks_in_attendees() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            2,
+        )
+        .await;
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                3,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotEnoughTicketsToCheckIn);
+
+    harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                2,
+            )],
+            &[&attendee],
+        )
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.used, 2);
+    assert_eq!(harness.token_balance(ticket_vault).await, 0);
+}
+
+#[tokio::test]
+async fn checks_in_attendees_with_proof_of_attendance() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            1,
+        )
+        .await;
+
+    harness
+        .process(
+            &[instructions::create_proof_of_attendance(
+                authority,
+                event_base,
+                event_ticket_base,
+                "Tomorrowland 2022 - POAP".to_string(),
+                "TMRWP".to_string(),
+                "https://tomorrowland.com/poap.json".to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await;
+    assert_error(
+        result,
+        ErrorCode::CheckInIsOnlyAvailableForTicketsWithoutAttendance,
+    );
+
+    // the attendee's associated POAP account is created on check-in
+    let attendance_vault = get_associated_token_address(
+        &attendee.pubkey(),
+        &attendance_mint(&event_base, &event_ticket_base),
+    );
+
+    harness
+        .process(
+            &[instructions::check_in_with_attendance(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(ticket_vault).await, 0);
+    assert_eq!(harness.token_balance(attendance_vault).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_check_ins_by_strangers() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let attendee = harness.create_user().await;
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(
+            &event_base,
+            &event_ticket_base,
+            &attendee,
+            &attendee_vault,
+            1,
+        )
+        .await;
+    let stranger = Keypair::new();
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                stranger.pubkey(),
+                stranger.pubkey(),
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&stranger, &attendee],
+        )
+        .await;
+    assert_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn only_uses_ticket_vaults_of_the_holder() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let stranger = harness.create_user().await;
+    let fee_vault = harness.fee_vault;
+
+    // tickets can't be sent to a vault other than the buyer's associated one
+    let other_vault = harness
+        .create_token_account(
+            &ticket_mint(&event_base, &event_ticket_base),
+            &buyer.pubkey(),
+        )
+        .await;
+    let mut buy_tickets = instructions::buy_tickets(
+        buyer.pubkey(),
+        buyer.pubkey(),
+        buyer.pubkey(),
+        event_base,
+        event_ticket_base,
+        buyer_vault,
+        fee_vault,
+        1,
+    );
+    let ticket_vault = get_associated_token_address(
+        &buyer.pubkey(),
+        &ticket_mint(&event_base, &event_ticket_base),
+    );
+    for meta in buy_tickets.accounts.iter_mut() {
+        if meta.pubkey == ticket_vault {
+            meta.pubkey = other_vault;
+        }
+    }
+    let result = harness.process(&[buy_tickets], &[&buyer]).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountNotAssociatedTokenAccount,
+    );
+
+    // the buyer's associated vault is created on their first purchase
+    assert!(harness.account(ticket_vault).await.is_none());
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                stranger.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidTicketVaultOwner);
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_zero_quantities() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+    let fee_vault = harness.fee_vault;
+
+    let result = harness
+        .process(
+            &[instructions::create_event_ticket(
+                authority,
+                event_base,
+                Keypair::new().pubkey(),
+                TICKET_NAME.to_string(),
+                TICKET_SYMBOL.to_string(),
+                TICKET_URI.to_string(),
+                5,
+                0,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidQuantity);
+
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                0,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidQuantity);
+
+    let result = harness
+        .process(
+            &[instructions::issue_comp_tickets(
+                authority,
+                event_base,
+                event_ticket_base,
+                buyer.pubkey(),
+                0,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidQuantity);
+
+    let result = harness
+        .process(
+            &[instructions::check_in(
+                authority,
+                authority,
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                0,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidQuantity);
+}
+
+#[tokio::test]
+async fn rejects_overflowing_amounts() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, u32::MAX).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+
+    let result = harness
+        .process(
+            &[instructions::create_event_ticket(
+                authority,
+                event_base,
+                Keypair::new().pubkey(),
+                TICKET_NAME.to_string(),
+                TICKET_SYMBOL.to_string(),
+                TICKET_URI.to_string(),
+                5,
+                1,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::ArithmeticOverflow);
+
+    harness
+        .process(
+            &[instructions::set_ticket_pricing_curve(
+                authority,
+                event_base,
+                event_ticket_base,
+                Some(PricingCurve::Linear {
+                    increment: u32::MAX,
+                }),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let fee_vault = harness.fee_vault;
+    let result = harness
+        .process(
+            &[instructions::buy_tickets(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                2,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::ArithmeticOverflow);
+}
+
+#[tokio::test]
+async fn buys_tickets_for_another_wallet() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    let parent = harness.create_user().await;
+    let parent_vault = harness.create_wallet(&parent.pubkey(), 100).await;
+    let child = harness.create_user().await;
+    let child_vault = harness.create_wallet(&child.pubkey(), 100).await;
+    let fee_vault = harness.fee_vault;
+
+    let set_wallet_limit = |authority, max_per_wallet| {
+        instructions::set_ticket_wallet_limit(
+            authority,
+            event_base,
+            event_ticket_base,
+            max_per_wallet,
+        )
+    };
+    let result = harness
+        .process(&[set_wallet_limit(parent.pubkey(), 2)], &[&parent])
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetWalletLimit);
+    harness
+        .process(&[set_wallet_limit(authority, 2)], &[])
+        .await
+        .unwrap();
+
+    let buy_tickets = |payer: &Keypair, buyer_vault, recipient: &Keypair, ticket_quantity| {
+        instructions::buy_tickets(
+            payer.pubkey(),
+            payer.pubkey(),
+            recipient.pubkey(),
+            event_base,
+            event_ticket_base,
+            buyer_vault,
+            fee_vault,
+            ticket_quantity,
+        )
+    };
+
+    harness
+        .process(&[buy_tickets(&parent, parent_vault, &child, 2)], &[&parent])
+        .await
+        .unwrap();
+
+    let ticket_mint = ticket_mint(&event_base, &event_ticket_base);
+    let child_tickets = get_associated_token_address(&child.pubkey(), &ticket_mint);
+    let parent_tickets = get_associated_token_address(&parent.pubkey(), &ticket_mint);
+    assert_eq!(harness.token_balance(child_tickets).await, 2);
+    assert_eq!(harness.token_balance(parent_vault).await, 90);
+    assert_eq!(harness.token_balance(child_vault).await, 100);
+    assert!(harness.account(parent_tickets).await.is_none());
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let wallet_purchase: WalletPurchase = harness
+        .fetch(find_wallet_purchase_address(&event_ticket_address, &child.pubkey()).0)
+        .await;
+    assert_eq!(wallet_purchase.quantity, 2);
+
+    // the limit follows the recipient, whoever pays
+    for (payer, buyer_vault) in [(&parent, parent_vault), (&child, child_vault)] {
+        let result = harness
+            .process(&[buy_tickets(payer, buyer_vault, &child, 1)], &[payer])
+            .await;
+        assert_error(result, ErrorCode::WalletLimitExceeded);
+    }
+
+    harness
+        .process(
+            &[buy_tickets(&parent, parent_vault, &parent, 2)],
+            &[&parent],
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(parent_tickets).await, 2);
+
+    harness
+        .process(&[set_wallet_limit(authority, 0)], &[])
+        .await
+        .unwrap();
+    harness
+        .process(&[buy_tickets(&child, child_vault, &child, 1)], &[&child])
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(child_tickets).await, 3);
+}
+
+#[tokio::test]
+async fn sponsors_purchases_and_check_ins() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 10).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    let relayer = harness.create_user().await;
+    let relayer_vault = harness.create_wallet(&relayer.pubkey(), 0).await;
+    let destination_vault = harness.create_wallet(&authority, 0).await;
+    let fee_vault = harness.fee_vault;
+    let event_address = event(&event_base);
+
+    // the attendee holds tokens but no SOL at all
+    let attendee = Keypair::new();
+    let attendee_vault = harness.create_wallet(&attendee.pubkey(), 100).await;
+
+    let set_event_relayer = |authority, relayer, relayer_fee| {
+        instructions::set_event_relayer(authority, event_base, relayer, relayer_fee)
+    };
+    let result = harness
+        .process(
+            &[set_event_relayer(
+                relayer.pubkey(),
+                Some(relayer.pubkey()),
+                1,
+            )],
+            &[&relayer],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetRelayer);
+    harness
+        .process(
+            &[set_event_relayer(authority, Some(relayer.pubkey()), 1)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(
+            &[instructions::create_proof_of_attendance(
+                authority,
+                event_base,
+                event_ticket_base,
+                "Tomorrowland 2022 - POAP".to_string(),
+                "TMRWP".to_string(),
+                "https://tomorrowland.com/poap.json".to_string(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(
+            &[instructions::buy_tickets(
+                relayer.pubkey(),
+                attendee.pubkey(),
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                attendee_vault,
+                fee_vault,
+                2,
+            )],
+            &[&relayer, &attendee],
+        )
+        .await
+        .unwrap();
+
+    let ticket_vault = get_associated_token_address(
+        &attendee.pubkey(),
+        &ticket_mint(&event_base, &event_ticket_base),
+    );
+    harness
+        .process(
+            &[instructions::check_in_with_attendance(
+                relayer.pubkey(),
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&relayer, &attendee],
+        )
+        .await
+        .unwrap();
+
+    let attendance_vault = get_associated_token_address(
+        &attendee.pubkey(),
+        &attendance_mint(&event_base, &event_ticket_base),
+    );
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+    assert_eq!(harness.token_balance(attendance_vault).await, 1);
+    assert!(harness.account(attendee.pubkey()).await.is_none());
+
+    // check-ins can be sponsored, but only purchases are reimbursed
+    harness
+        .process(
+            &[instructions::check_in_with_attendance(
+                authority,
+                authority,
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                ticket_vault,
+                1,
+            )],
+            &[&attendee],
+        )
+        .await
+        .unwrap();
+
+    let sponsored: Event = harness.fetch(event_address).await;
+    assert_eq!(sponsored.relayer_owed, 1);
+
+    let result = harness
+        .process(&[set_event_relayer(authority, None, 0)], &[])
+        .await;
+    assert_error(result, ErrorCode::RelayerHasUnclaimedReimbursements);
+
+    // what is owed to the relayer can't be withdrawn
+    let withdraw =
+        |amount| instructions::withdraw(authority, event_base, destination_vault, amount);
+    let result = harness.process(&[withdraw(10)], &[]).await;
+    assert_error(result, ErrorCode::InsufficientEventFunds);
+    harness.process(&[withdraw(9)], &[]).await.unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::claim_relayer_reimbursement(
+                authority,
+                event_base,
+                destination_vault,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyRelayerCanClaimReimbursements);
+    harness
+        .process(
+            &[instructions::claim_relayer_reimbursement(
+                relayer.pubkey(),
+                event_base,
+                relayer_vault,
+            )],
+            &[&relayer],
+        )
+        .await
+        .unwrap();
+
+    let sponsored: Event = harness.fetch(event_address).await;
+    assert_eq!(sponsored.relayer_owed, 0);
+    assert_eq!(harness.token_balance(relayer_vault).await, 1);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 0);
+
+    // the relayer is never owed more than the event made on the purchase
+    harness
+        .process(
+            &[set_event_relayer(authority, Some(relayer.pubkey()), 20)],
+            &[],
+        )
+        .await
+        .unwrap();
+    harness
+        .process(
+            &[instructions::buy_tickets(
+                relayer.pubkey(),
+                attendee.pubkey(),
+                attendee.pubkey(),
+                event_base,
+                event_ticket_base,
+                attendee_vault,
+                fee_vault,
+                1,
+            )],
+            &[&relayer, &attendee],
+        )
+        .await
+        .unwrap();
+
+    let sponsored: Event = harness.fetch(event_address).await;
+    assert_eq!(sponsored.relayer_owed, 5);
+
+    harness
+        .process(
+            &[instructions::claim_relayer_reimbursement(
+                relayer.pubkey(),
+                event_base,
+                relayer_vault,
+            )],
+            &[&relayer],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(&[set_event_relayer(authority, None, 0)], &[])
+        .await
+        .unwrap();
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::ErrorCode;
+use disco_client::{
+    accounts::{Event, Venue},
+    instructions,
+    pda::find_venue_address,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+async fn create_venue(harness: &mut Harness, capacity: u32) -> Pubkey {
+    let venue_base = Keypair::new().pubkey();
+    let authority = harness.payer();
+
+    harness
+        .process(
+            &[instructions::create_venue(
+                authority,
+                venue_base,
+                "De Schorre".to_string(),
+                "https://goo.gl/maps/boom".to_string(),
+                capacity,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    find_venue_address(&venue_base).0
+}
+
+#[tokio::test]
+async fn creates_venue() {
+    let mut harness = Harness::start().await;
+    let venue = create_venue(&mut harness, 100).await;
+
+    let venue: Venue = harness.fetch(venue).await;
+    assert_eq!(venue.authority, harness.payer());
+    assert_eq!(venue.name, "De Schorre");
+    assert_eq!(venue.capacity, 100);
+}
+
+#[tokio::test]
+async fn rejects_invalid_venues() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+
+    for (name, location_uri, capacity, error) in [
+        ("a".repeat(33), "uri".to_string(), 1, ErrorCode::NameTooLong),
+        (
+            "De Schorre".to_string(),
+            "a".repeat(201),
+            1,
+            ErrorCode::UriTooLong,
+        ),
+        (
+            "De Schorre".to_string(),
+            "uri".to_string(),
+            0,
+            ErrorCode::InvalidVenueCapacity,
+        ),
+    ] {
+        let result = harness
+            .process(
+                &[instructions::create_venue(
+                    authority,
+                    Keypair::new().pubkey(),
+                    name,
+                    location_uri,
+                    capacity,
+                )],
+                &[],
+            )
+            .await;
+        assert_error(result, error);
+    }
+}
+
+#[tokio::test]
+async fn enforces_venue_capacity_across_tiers() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let venue = create_venue(&mut harness, 10).await;
+    let event_base = harness.create_event().await;
+    harness.create_event_ticket(&event_base, 5, 6).await;
+
+    harness
+        .process(
+            &[instructions::set_event_venue(authority, event_base, venue)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let event: Event = harness.fetch(event(&event_base)).await;
+    assert_eq!(event.venue, Some(venue));
+    assert_eq!(event.total_capacity, 10);
+    assert_eq!(event.total_allocated, 6);
+
+    harness.create_event_ticket(&event_base, 5, 4).await;
+
+    let result = harness
+        .process(
+            &[instructions::create_event_ticket(
+                authority,
+                event_base,
+                Keypair::new().pubkey(),
+                TICKET_NAME.to_string(),
+                TICKET_SYMBOL.to_string(),
+                TICKET_URI.to_string(),
+                5,
+                1,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::VenueCapacityExceeded);
+}
+
+#[tokio::test]
+async fn rejects_venues_smaller_than_the_allocation() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let venue = create_venue(&mut harness, 5).await;
+    let event_base = harness.create_event().await;
+    harness.create_event_ticket(&event_base, 5, 6).await;
+
+    let result = harness
+        .process(
+            &[instructions::set_event_venue(authority, event_base, venue)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::VenueCapacityExceeded);
+
+    let stranger = harness.create_user().await;
+    let result = harness
+        .process(
+            &[instructions::set_event_venue(
+                stranger.pubkey(),
+                event_base,
+                venue,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanSetVenue);
+}
+
+#[tokio::test]
+async fn rejects_venues_of_other_authorities() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let stranger = harness.create_user().await;
+    let venue_base = Keypair::new().pubkey();
+
+    harness
+        .process(
+            &[instructions::create_venue(
+                stranger.pubkey(),
+                venue_base,
+                "De Schorre".to_string(),
+                "https://goo.gl/maps/boom".to_string(),
+                100,
+            )],
+            &[&stranger],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .process(
+            &[instructions::set_event_venue(
+                authority,
+                event_base,
+                find_venue_address(&venue_base).0,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyVenueAuthorityCanHostEvents);
+}
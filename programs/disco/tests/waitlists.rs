@@ -0,0 +1,553 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use disco::{DiscountKind, ErrorCode};
+use disco_client::{
+    accounts::{EventTicket, TicketHold, Waitlist, WalletPurchase},
+    instructions,
+    pda::{find_waitlist_address, find_waitlist_entry_address, find_wallet_purchase_address},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    hash::hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+struct Waiter {
+    owner: Keypair,
+    buyer_vault: Pubkey,
+    ticket_vault: Pubkey,
+}
+
+async fn create_waitlist(harness: &mut Harness, event_base: &Pubkey, event_ticket_base: &Pubkey) {
+    let authority = harness.payer();
+    let accepted_mint = harness.accepted_mint;
+
+    harness
+        .process(
+            &[instructions::create_waitlist(
+                authority,
+                *event_base,
+                *event_ticket_base,
+                accepted_mint,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+}
+
+async fn create_waiter(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+) -> Waiter {
+    let owner = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&owner.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(event_base, event_ticket_base, &owner.pubkey())
+        .await;
+
+    Waiter {
+        owner,
+        buyer_vault,
+        ticket_vault,
+    }
+}
+
+async fn join_waitlist(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    waiter: &Waiter,
+) -> solana_sdk::transport::Result<()> {
+    let waitlist: Waitlist = harness
+        .fetch(find_waitlist_address(&event_ticket(event_base, event_ticket_base)).0)
+        .await;
+
+    harness
+        .process(
+            &[instructions::join_waitlist(
+                waiter.owner.pubkey(),
+                waiter.owner.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                waitlist.tail,
+                waiter.buyer_vault,
+            )],
+            &[&waiter.owner],
+        )
+        .await
+}
+
+async fn leave_waitlist(
+    harness: &mut Harness,
+    event_base: &Pubkey,
+    event_ticket_base: &Pubkey,
+    waiter: &Waiter,
+    position: u64,
+) -> solana_sdk::transport::Result<()> {
+    harness
+        .process(
+            &[instructions::leave_waitlist(
+                waiter.owner.pubkey(),
+                *event_base,
+                *event_ticket_base,
+                waiter.buyer_vault,
+                position,
+            )],
+            &[&waiter.owner],
+        )
+        .await
+}
+
+#[tokio::test]
+async fn releases_tickets_to_the_waitlist() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    let stranger = harness.create_user().await;
+    let accepted_mint = harness.accepted_mint;
+
+    let result = harness
+        .process(
+            &[instructions::create_waitlist(
+                stranger.pubkey(),
+                event_base,
+                event_ticket_base,
+                accepted_mint,
+            )],
+            &[&stranger],
+        )
+        .await;
+    assert_error(result, ErrorCode::OnlyEventAuthorityCanCreateWaitlists);
+
+    create_waitlist(&mut harness, &event_base, &event_ticket_base).await;
+    let waiter = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+
+    let result = join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter).await;
+    assert_error(result, ErrorCode::TicketsAreStillAvailable);
+
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter)
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(waiter.buyer_vault).await, 95);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.waitlisted, 1);
+
+    harness
+        .process(
+            &[instructions::release_ticket(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                0,
+                waiter.owner.pubkey(),
+                ticket_vault,
+                waiter.ticket_vault,
+                buyer_vault,
+                harness.fee_vault,
+            )],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(ticket_vault).await, 0);
+    assert_eq!(harness.token_balance(buyer_vault).await, 100);
+    assert_eq!(harness.token_balance(waiter.ticket_vault).await, 1);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 5);
+
+    let event_ticket: EventTicket = harness.fetch(event_ticket_address).await;
+    assert_eq!(event_ticket.sold, 1);
+    assert_eq!(event_ticket.waitlisted, 0);
+
+    let waitlist = find_waitlist_address(&event_ticket_address).0;
+    let waitlist_entry = find_waitlist_entry_address(&waitlist, 0).0;
+    assert!(harness.account(waitlist_entry).await.is_none());
+}
+
+#[tokio::test]
+async fn refunds_released_tickets_up_to_the_price_paid() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let fee_treasury = harness.fee_treasury.pubkey();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 20, 3).await;
+    harness.add_self_as_collaborator(&event_base).await;
+    create_waitlist(&mut harness, &event_base, &event_ticket_base).await;
+
+    harness
+        .process(
+            &[
+                instructions::set_platform_fee(authority, 1_000, fee_treasury),
+                instructions::create_promo(
+                    authority,
+                    event_base,
+                    hash(b"HALF").to_bytes(),
+                    DiscountKind::Fixed,
+                    10,
+                    1,
+                    None,
+                    None,
+                ),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &buyer.pubkey())
+        .await;
+    let fee_vault = harness.fee_vault;
+    harness
+        .process(
+            &[instructions::buy_tickets_with_promo(
+                buyer.pubkey(),
+                buyer.pubkey(),
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                buyer_vault,
+                fee_vault,
+                "HALF".to_string(),
+                1,
+            )],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(buyer_vault).await, 90);
+
+    let guest = harness.create_user().await;
+    let guest_vault = harness.create_wallet(&guest.pubkey(), 0).await;
+    let guest_ticket_vault = harness
+        .create_ticket_vault(&event_base, &event_ticket_base, &guest.pubkey())
+        .await;
+    harness
+        .process(
+            &[
+                instructions::issue_comp_tickets(
+                    authority,
+                    event_base,
+                    event_ticket_base,
+                    guest.pubkey(),
+                    1,
+                ),
+                instructions::issue_comp_tickets(
+                    authority,
+                    event_base,
+                    event_ticket_base,
+                    buyer.pubkey(),
+                    1,
+                ),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let waiter = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter)
+        .await
+        .unwrap();
+
+    // comp tickets can't be cashed out through the waitlist
+    let result = harness
+        .process(
+            &[instructions::release_ticket(
+                guest.pubkey(),
+                event_base,
+                event_ticket_base,
+                0,
+                waiter.owner.pubkey(),
+                guest_ticket_vault,
+                waiter.ticket_vault,
+                guest_vault,
+                fee_vault,
+            )],
+            &[&guest],
+        )
+        .await;
+    assert_error(result, ErrorCode::TicketWasNotPurchased);
+
+    let result = harness
+        .process(
+            &[instructions::release_ticket(
+                guest.pubkey(),
+                event_base,
+                event_ticket_base,
+                0,
+                waiter.owner.pubkey(),
+                ticket_vault,
+                waiter.ticket_vault,
+                guest_vault,
+                fee_vault,
+            )],
+            &[&guest],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidTicketVaultOwner);
+
+    harness
+        .process(
+            &[instructions::release_ticket(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                0,
+                waiter.owner.pubkey(),
+                ticket_vault,
+                waiter.ticket_vault,
+                buyer_vault,
+                fee_vault,
+            )],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    // the discounted price comes back minus the platform fee, the event keeps
+    // the rest of the waitlisted payment
+    assert_eq!(harness.token_balance(buyer_vault).await, 99);
+    assert_eq!(harness.token_balance(waiter.ticket_vault).await, 1);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 18);
+    assert_eq!(harness.token_balance(fee_vault).await, 3);
+
+    let event_ticket_address = event_ticket(&event_base, &event_ticket_base);
+    let wallet_purchase: WalletPurchase = harness
+        .fetch(find_wallet_purchase_address(&event_ticket_address, &buyer.pubkey()).0)
+        .await;
+    assert_eq!(wallet_purchase.quantity, 0);
+    assert_eq!(wallet_purchase.amount_paid, 0);
+
+    // only the paid ticket could be released, the comp stays behind
+    assert_eq!(wallet_purchase.comps, 1);
+    assert_eq!(harness.token_balance(ticket_vault).await, 1);
+}
+
+#[tokio::test]
+async fn leaves_and_skips_waitlist_entries() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    create_waitlist(&mut harness, &event_base, &event_ticket_base).await;
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    let ticket_vault = harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+    let first = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+    let second = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &first)
+        .await
+        .unwrap();
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &second)
+        .await
+        .unwrap();
+
+    let result = leave_waitlist(&mut harness, &event_base, &event_ticket_base, &second, 0).await;
+    assert_error(result, ErrorCode::OnlyWaitlistEntryOwnerCanLeave);
+
+    let result = harness
+        .process(
+            &[instructions::skip_waitlist_entry(
+                event_base,
+                event_ticket_base,
+                0,
+                first.owner.pubkey(),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::WaitlistEntryIsActive);
+
+    leave_waitlist(&mut harness, &event_base, &event_ticket_base, &first, 0)
+        .await
+        .unwrap();
+    assert_eq!(harness.token_balance(first.buyer_vault).await, 100);
+
+    let result = leave_waitlist(&mut harness, &event_base, &event_ticket_base, &first, 0).await;
+    assert_error(result, ErrorCode::WaitlistEntryHasLeft);
+
+    let result = harness
+        .process(
+            &[instructions::release_ticket(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                0,
+                first.owner.pubkey(),
+                ticket_vault,
+                first.ticket_vault,
+                buyer_vault,
+                harness.fee_vault,
+            )],
+            &[&buyer],
+        )
+        .await;
+    assert_error(result, ErrorCode::WaitlistEntryHasLeft);
+
+    harness
+        .process(
+            &[instructions::skip_waitlist_entry(
+                event_base,
+                event_ticket_base,
+                0,
+                first.owner.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness
+        .process(
+            &[instructions::release_ticket(
+                buyer.pubkey(),
+                event_base,
+                event_ticket_base,
+                1,
+                second.owner.pubkey(),
+                ticket_vault,
+                second.ticket_vault,
+                buyer_vault,
+                harness.fee_vault,
+            )],
+            &[&buyer],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(harness.token_balance(first.ticket_vault).await, 0);
+    assert_eq!(harness.token_balance(second.ticket_vault).await, 1);
+    assert_eq!(harness.token_balance(second.buyer_vault).await, 95);
+}
+
+#[tokio::test]
+async fn allocates_expired_holds_to_the_waitlist() {
+    let mut harness = Harness::start().await;
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+    create_waitlist(&mut harness, &event_base, &event_ticket_base).await;
+    let holder = harness.create_user().await;
+
+    harness
+        .process(
+            &[instructions::reserve_tickets(
+                holder.pubkey(),
+                event_base,
+                event_ticket_base,
+                1,
+            )],
+            &[&holder],
+        )
+        .await
+        .unwrap();
+
+    let waiter = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter)
+        .await
+        .unwrap();
+
+    let allocate = instructions::allocate_waitlist_entry(
+        event_base,
+        event_ticket_base,
+        0,
+        waiter.owner.pubkey(),
+        waiter.ticket_vault,
+        harness.fee_vault,
+    );
+
+    let result = harness.process(std::slice::from_ref(&allocate), &[]).await;
+    assert_error(result, ErrorCode::NotEnoughTicketsAvailable);
+
+    let now = harness.now().await;
+    harness.set_time(now + TicketHold::DURATION + 1).await;
+    harness
+        .process(
+            &[instructions::release_expired_hold(
+                event_base,
+                event_ticket_base,
+                holder.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    harness.process(&[allocate], &[]).await.unwrap();
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.sold, 1);
+    assert_eq!(event_ticket.held, 0);
+    assert_eq!(event_ticket.waitlisted, 0);
+    assert_eq!(harness.token_balance(waiter.ticket_vault).await, 1);
+    assert_eq!(harness.token_balance(event_vault(&event_base)).await, 5);
+}
+
+#[tokio::test]
+async fn applies_wallet_limit_to_the_waitlist() {
+    let mut harness = Harness::start().await;
+    let authority = harness.payer();
+    let event_base = harness.create_event().await;
+    let event_ticket_base = harness.create_event_ticket(&event_base, 5, 1).await;
+
+    harness
+        .process(
+            &[instructions::set_ticket_wallet_limit(
+                authority,
+                event_base,
+                event_ticket_base,
+                1,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    create_waitlist(&mut harness, &event_base, &event_ticket_base).await;
+
+    let buyer = harness.create_user().await;
+    let buyer_vault = harness.create_wallet(&buyer.pubkey(), 100).await;
+    harness
+        .buy_tickets(&event_base, &event_ticket_base, &buyer, &buyer_vault, 1)
+        .await;
+
+    let waiter = create_waiter(&mut harness, &event_base, &event_ticket_base).await;
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter)
+        .await
+        .unwrap();
+
+    // a waiting entry counts as a ticket until the wallet leaves
+    let result = join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter).await;
+    assert_error(result, ErrorCode::WalletLimitExceeded);
+
+    leave_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter, 0)
+        .await
+        .unwrap();
+    join_waitlist(&mut harness, &event_base, &event_ticket_base, &waiter)
+        .await
+        .unwrap();
+
+    let event_ticket: EventTicket = harness
+        .fetch(event_ticket(&event_base, &event_ticket_base))
+        .await;
+    assert_eq!(event_ticket.waitlisted, 1);
+}
@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Dumps the programs the Rust integration tests load besides `disco` itself
+# into programs/disco/tests/fixtures, which is where solana-program-test looks
+# for them. They're build artifacts, so they're ignored rather than committed.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+fixtures=programs/disco/tests/fixtures
+mkdir -p "$fixtures"
+
+# Same cluster Anchor.toml clones the metadata program from for the TS suite.
+solana program dump --url devnet \
+    metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s \
+    "$fixtures/mpl_token_metadata.so"
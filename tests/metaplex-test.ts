@@ -14,6 +14,7 @@ import {
 } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { assert } from "chai";
+import { createHash } from "crypto";
 import { Disco } from "../target/types/disco";
 import {
   createFundedWallet,
@@ -31,6 +32,7 @@ describe("disco", () => {
   const eventGeneralTicketBaseKeypair = anchor.web3.Keypair.generate();
   const eventVipTicketBaseKeypair = anchor.web3.Keypair.generate();
   const collaborator1Keypair = anchor.web3.Keypair.generate();
+  const feeTreasuryKeypair = anchor.web3.Keypair.generate();
   const metadataProgramPublicKey = new anchor.web3.PublicKey(
     "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
   );
@@ -52,6 +54,7 @@ describe("disco", () => {
   let vipAttendanceMintPublicKey: anchor.web3.PublicKey;
   let collaborator1PublicKey: anchor.web3.PublicKey;
   let aliceVipAttendanceAssociatedTokenPublicKey: anchor.web3.PublicKey;
+  let feeVaultPublicKey: anchor.web3.PublicKey;
 
   before(async () => {
     [eventPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
@@ -123,6 +126,12 @@ describe("disco", () => {
       aliceBalance,
       aliceKeypair
     );
+    feeVaultPublicKey = await createUserAndAssociatedWallet(
+      provider,
+      acceptedMintPublicKey,
+      0,
+      feeTreasuryKeypair
+    );
     aliceGeneralTicketAssociatedTokenPublicKey =
       await getAssociatedTokenAddress(
         eventGeneralTicketMintPublicKey,
@@ -139,6 +148,35 @@ describe("disco", () => {
       );
   });
 
+  it("should initialize the protocol config", async () => {
+    // arrange
+    const [configPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("config", "utf-8")],
+      program.programId
+    );
+    const [programDataPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [program.programId.toBuffer()],
+        anchor.web3.BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+      );
+    // act
+    await program.methods
+      .initializeConfig(0, feeTreasuryKeypair.publicKey)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        programData: programDataPublicKey,
+      })
+      .rpc();
+    // assert
+    const configAccount = await program.account.config.fetch(configPublicKey);
+    assert.isTrue(configAccount.admin.equals(provider.wallet.publicKey));
+    assert.equal(configAccount.feeBps, 0);
+    assert.isTrue(
+      configAccount.feeTreasury.equals(feeTreasuryKeypair.publicKey)
+    );
+    assert.isFalse(configAccount.paused);
+  });
+
   it("should create Tomorrowland 2022 event", async () => {
     // arrange
     const eventTitle = "Tomorrowland 2022";
@@ -330,6 +368,7 @@ describe("disco", () => {
     assert.equal(eventGeneralTicketAccount.price, ticketPrice);
     assert.equal(eventGeneralTicketAccount.quantity, ticketQuantity);
     assert.equal(eventGeneralTicketAccount.sold, 0);
+    assert.equal(eventGeneralTicketAccount.comps, 0);
     assert.isFalse(eventGeneralTicketAccount.hasPoap);
     assert.isDefined(ticketMintAccount);
     assert.equal(ticketMintAccount.decimals, 0);
@@ -484,24 +523,30 @@ describe("disco", () => {
     );
     await Promise.all([
       program.methods
-        .buyTickets(generalTicketQuantity)
+        .buyTickets(generalTicketQuantity, null, null)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           authority: aliceKeypair.publicKey,
+          recipient: aliceKeypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,
           eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
           buyerVault: aliceAssociatedWalletPublicKey,
           ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+          feeVault: feeVaultPublicKey,
         })
         .signers([aliceKeypair])
         .rpc(),
       program.methods
-        .buyTickets(vipTicketQuantity)
+        .buyTickets(vipTicketQuantity, null, null)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           authority: aliceKeypair.publicKey,
+          recipient: aliceKeypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,
           eventTicketBase: eventVipTicketBaseKeypair.publicKey,
           buyerVault: aliceAssociatedWalletPublicKey,
           ticketVault: aliceVipTicketAssociatedTokenPublicKey,
+          feeVault: feeVaultPublicKey,
         })
         .signers([aliceKeypair])
         .rpc(),
@@ -615,6 +660,7 @@ describe("disco", () => {
       program.methods
         .checkIn(generalTicketQuantity)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           attendee: aliceKeypair.publicKey,
           collaboratorBase: collaborator1Keypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,
@@ -626,6 +672,7 @@ describe("disco", () => {
       program.methods
         .checkInWithAttendance(vipTicketQuantity)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           attendee: aliceKeypair.publicKey,
           collaboratorBase: collaborator1Keypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,
@@ -689,6 +736,1668 @@ describe("disco", () => {
     );
   });
 
+  it("should issue 3 comp general tickets to bob", async () => {
+    // arrange
+    const compTicketQuantity = 3;
+    const bobKeypair = anchor.web3.Keypair.generate();
+    const bobGeneralTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventGeneralTicketMintPublicKey,
+        bobKeypair.publicKey
+      );
+    const beforeEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    const beforeEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    // act
+    await program.methods
+      .issueCompTickets(compTicketQuantity)
+      .accounts({
+        authority: collaborator1Keypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+        collaborator: collaborator1PublicKey,
+        recipient: bobKeypair.publicKey,
+        recipientVault: bobGeneralTicketAssociatedTokenPublicKey,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          bobGeneralTicketAssociatedTokenPublicKey,
+          bobKeypair.publicKey,
+          eventGeneralTicketMintPublicKey
+        ),
+      ])
+      .signers([collaborator1Keypair])
+      .rpc();
+    // assert
+    const afterEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    const afterEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    const bobGeneralTicketVaultAccount = await getAccount(
+      provider.connection,
+      bobGeneralTicketAssociatedTokenPublicKey
+    );
+    assert.equal(
+      afterEventGeneralTicketAccount.comps,
+      beforeEventGeneralTicketAccount.comps + compTicketQuantity
+    );
+    assert.equal(
+      afterEventGeneralTicketAccount.sold,
+      beforeEventGeneralTicketAccount.sold
+    );
+    assert.equal(afterEventVaultAccount.amount, beforeEventVaultAccount.amount);
+    assert.equal(
+      bobGeneralTicketVaultAccount.amount,
+      BigInt(compTicketQuantity)
+    );
+  });
+
+  it("should fail on unauthorized comp ticket issuance", async () => {
+    // arrange
+    let error: AnchorError;
+    const [aliceCollaboratorPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("collaborator", "utf-8"),
+          eventPublicKey.toBuffer(),
+          aliceKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    // act
+    try {
+      await program.methods
+        .issueCompTickets(1)
+        .accounts({
+          authority: aliceKeypair.publicKey,
+          eventBase: eventBaseKeypair.publicKey,
+          eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+          collaborator: aliceCollaboratorPublicKey,
+          recipient: aliceKeypair.publicKey,
+          recipientVault: aliceGeneralTicketAssociatedTokenPublicKey,
+        })
+        .signers([aliceKeypair])
+        .rpc();
+    } catch (err) {
+      error = err;
+    }
+    // assert
+    assert.isDefined(error);
+    assert.equal(
+      error.error.errorCode.code,
+      "OnlyEventAuthorityOrCollaboratorsCanIssueCompTickets"
+    );
+  });
+
+  it("should buy 5 general tickets with a 20% promo", async () => {
+    // arrange
+    const generalTicketQuantity = 5;
+    const discountValue = 20;
+    const codeHash = createHash("sha256").update("TMRLND20").digest();
+    const [promoPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("promo", "utf-8"), eventPublicKey.toBuffer(), codeHash],
+      program.programId
+    );
+    const beforeAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    const beforeEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    // act
+    await program.methods
+      .createPromo(
+        [...codeHash],
+        { percentage: {} },
+        discountValue,
+        generalTicketQuantity,
+        eventGeneralTicketPublicKey,
+        null
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .buyTickets(generalTicketQuantity, "TMRLND20", null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .remainingAccounts([
+        { pubkey: promoPublicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const afterAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    const afterEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    const promoAccount = await program.account.promo.fetch(promoPublicKey);
+    assert.equal(
+      afterAliceAccount.amount,
+      beforeAliceAccount.amount -
+        BigInt(
+          (generalTicketQuantity *
+            afterEventGeneralTicketAccount.price *
+            (100 - discountValue)) /
+            100
+        )
+    );
+    assert.equal(
+      afterEventGeneralTicketAccount.sold,
+      beforeEventGeneralTicketAccount.sold + generalTicketQuantity
+    );
+    assert.equal(promoAccount.redemptions, generalTicketQuantity);
+  });
+
+  it("should fail when promo has no redemptions left", async () => {
+    // arrange
+    let error: AnchorError;
+    const codeHash = createHash("sha256").update("TMRLND20").digest();
+    const [promoPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("promo", "utf-8"), eventPublicKey.toBuffer(), codeHash],
+      program.programId
+    );
+    // act
+    try {
+      await program.methods
+        .buyTickets(1, "TMRLND20", null)
+        .accounts({
+          feePayer: provider.wallet.publicKey,
+          authority: aliceKeypair.publicKey,
+          recipient: aliceKeypair.publicKey,
+          eventBase: eventBaseKeypair.publicKey,
+          eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+          buyerVault: aliceAssociatedWalletPublicKey,
+          ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+          feeVault: feeVaultPublicKey,
+        })
+        .remainingAccounts([
+          { pubkey: promoPublicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([aliceKeypair])
+        .rpc();
+    } catch (err) {
+      error = err;
+    }
+    // assert
+    assert.isDefined(error);
+    assert.equal(error.error.errorCode.code, "PromoHasNoRedemptionsLeft");
+  });
+
+  it("should buy 2 general tickets through an affiliate", async () => {
+    // arrange
+    const generalTicketQuantity = 2;
+    const commissionBps = 1000;
+    const promoterKeypair = await createFundedWallet(provider);
+    const promoterAssociatedWalletPublicKey =
+      await createUserAndAssociatedWallet(
+        provider,
+        acceptedMintPublicKey,
+        0,
+        promoterKeypair
+      );
+    const [affiliatePublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("affiliate", "utf-8"),
+        eventPublicKey.toBuffer(),
+        promoterKeypair.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const beforeEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    // act
+    await program.methods
+      .createAffiliate(commissionBps)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        affiliateBase: promoterKeypair.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .buyTickets(generalTicketQuantity, null, promoterKeypair.publicKey)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .remainingAccounts([
+        { pubkey: affiliatePublicKey, isSigner: false, isWritable: true },
+        {
+          pubkey: promoterAssociatedWalletPublicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const eventGeneralTicketAccount = await program.account.eventTicket.fetch(
+      eventGeneralTicketPublicKey
+    );
+    const afterEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    const promoterAccount = await getAccount(
+      provider.connection,
+      promoterAssociatedWalletPublicKey
+    );
+    const affiliateAccount = await program.account.affiliate.fetch(
+      affiliatePublicKey
+    );
+    const amount = generalTicketQuantity * eventGeneralTicketAccount.price;
+    const commission = Math.floor((amount * commissionBps) / 10000);
+    assert.equal(promoterAccount.amount, BigInt(commission));
+    assert.equal(
+      afterEventVaultAccount.amount,
+      beforeEventVaultAccount.amount + BigInt(amount - commission)
+    );
+    assert.equal(affiliateAccount.ticketsSold, generalTicketQuantity);
+    assert.isTrue(affiliateAccount.commissionEarned.eq(new BN(commission)));
+  });
+
+  it("should sell early bird tickets through a dutch auction", async () => {
+    // arrange
+    const ticketQuantity = 3;
+    const startPrice = 50;
+    const floorPrice = 10;
+    const eventEarlyBirdTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventEarlyBirdTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventEarlyBirdTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventEarlyBirdTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventEarlyBirdTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [auctionReceiptPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("auction_receipt", "utf-8"),
+          eventEarlyBirdTicketPublicKey.toBuffer(),
+          aliceKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const aliceEarlyBirdTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventEarlyBirdTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const beforeAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Early Bird",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        startPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventEarlyBirdTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .setTicketAuction(
+            startPrice,
+            floorPrice,
+            new BN(Math.floor(Date.now() / 1000) - 3600),
+            new BN(60),
+            5,
+            true
+          )
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventEarlyBirdTicketBaseKeypair.publicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceEarlyBirdTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventEarlyBirdTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buyAuctionTickets(ticketQuantity)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventEarlyBirdTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        feeVault: feeVaultPublicKey,
+        ticketVault: aliceEarlyBirdTicketAssociatedTokenPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    await program.methods
+      .settleAuction()
+      .accounts({
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventEarlyBirdTicketBaseKeypair.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .claimAuctionRebate()
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventEarlyBirdTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const eventEarlyBirdTicketAccount =
+      await program.account.eventTicket.fetch(eventEarlyBirdTicketPublicKey);
+    const afterAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    const auctionReceiptAccount =
+      await program.account.auctionReceipt.fetchNullable(
+        auctionReceiptPublicKey
+      );
+    assert.equal(eventEarlyBirdTicketAccount.sold, ticketQuantity);
+    assert.equal(
+      eventEarlyBirdTicketAccount.auction.clearingPrice,
+      floorPrice
+    );
+    assert.equal(
+      afterAliceAccount.amount,
+      beforeAliceAccount.amount - BigInt(ticketQuantity * floorPrice)
+    );
+    assert.isNull(auctionReceiptAccount);
+  });
+
+  it("should charge each ticket along a stepped pricing curve", async () => {
+    // arrange
+    const ticketPrice = 10;
+    const ticketQuantity = 10;
+    const ticketToBuy = 3;
+    const stepSize = 2;
+    const increment = 5;
+    const eventBackstageTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventBackstageTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventBackstageTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventBackstageTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventBackstageTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const aliceBackstageTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventBackstageTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const beforeAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Backstage",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventBackstageTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .setTicketPricingCurve({ stepped: { stepSize, increment } })
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventBackstageTicketBaseKeypair.publicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceBackstageTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventBackstageTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buyTickets(ticketToBuy, null, null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventBackstageTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceBackstageTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const afterAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    // tickets #0 and #1 are on the first step, ticket #2 on the second one
+    const expectedAmount = ticketPrice * 2 + (ticketPrice + increment);
+    assert.equal(
+      afterAliceAccount.amount,
+      beforeAliceAccount.amount - BigInt(expectedAmount)
+    );
+  });
+
+  it("should allocate lottery tickets through a raffle", async () => {
+    // arrange
+    const ticketPrice = 15;
+    const ticketQuantity = 5;
+    const secret = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    const commitment = createHash("sha256").update(secret).digest();
+    const now = Math.floor(Date.now() / 1000);
+    const eventLotteryTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventLotteryTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventLotteryTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventLotteryTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventLotteryTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [rafflePublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("raffle", "utf-8"), eventLotteryTicketPublicKey.toBuffer()],
+      program.programId
+    );
+    const aliceLotteryTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventLotteryTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const beforeAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Lottery",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .createRaffle(new BN(now - 60), new BN(now + 5), [...commitment])
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+            acceptedMint: acceptedMintPublicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceLotteryTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventLotteryTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .enterRaffle()
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 7000));
+    await program.methods
+      .sealRaffle()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+      })
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.methods
+      .drawRaffle([...secret])
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+        feeVault: feeVaultPublicKey,
+        slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .claimRaffleEntry()
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventLotteryTicketBaseKeypair.publicKey,
+        ticketVault: aliceLotteryTicketAssociatedTokenPublicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const raffleAccount = await program.account.raffle.fetch(rafflePublicKey);
+    const eventLotteryTicketAccount = await program.account.eventTicket.fetch(
+      eventLotteryTicketPublicKey
+    );
+    const afterAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    const aliceLotteryTicketVaultAccount = await getAccount(
+      provider.connection,
+      aliceLotteryTicketAssociatedTokenPublicKey
+    );
+    assert.isTrue(raffleAccount.drawn);
+    assert.equal(raffleAccount.entries, 1);
+    assert.equal(raffleAccount.winners, 1);
+    assert.equal(eventLotteryTicketAccount.sold, 1);
+    assert.equal(aliceLotteryTicketVaultAccount.amount, BigInt(1));
+    assert.equal(
+      afterAliceAccount.amount,
+      beforeAliceAccount.amount - BigInt(ticketPrice)
+    );
+  });
+
+  it("should allocate a released ticket to the waitlist", async () => {
+    // arrange
+    const ticketPrice = 25;
+    const ticketQuantity = 1;
+    const bobKeypair = await createFundedWallet(provider);
+    const bobAssociatedWalletPublicKey = await createUserAndAssociatedWallet(
+      provider,
+      acceptedMintPublicKey,
+      ticketPrice,
+      bobKeypair
+    );
+    const eventSoldOutTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventSoldOutTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventSoldOutTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventSoldOutTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventSoldOutTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [waitlistPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("waitlist", "utf-8"), eventSoldOutTicketPublicKey.toBuffer()],
+      program.programId
+    );
+    const aliceSoldOutTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventSoldOutTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const bobSoldOutTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventSoldOutTicketMintPublicKey,
+        bobKeypair.publicKey
+      );
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Sold Out",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventSoldOutTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .createWaitlist()
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventSoldOutTicketBaseKeypair.publicKey,
+            acceptedMint: acceptedMintPublicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceSoldOutTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventSoldOutTicketMintPublicKey
+        ),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          bobSoldOutTicketAssociatedTokenPublicKey,
+          bobKeypair.publicKey,
+          eventSoldOutTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buyTickets(ticketQuantity, null, null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventSoldOutTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceSoldOutTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    const beforeAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    await program.methods
+      .joinWaitlist()
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: bobKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventSoldOutTicketBaseKeypair.publicKey,
+        buyerVault: bobAssociatedWalletPublicKey,
+        ticketVault: bobSoldOutTicketAssociatedTokenPublicKey,
+      })
+      .signers([bobKeypair])
+      .rpc();
+    await program.methods
+      .releaseTicket()
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventSoldOutTicketBaseKeypair.publicKey,
+        owner: bobKeypair.publicKey,
+        ticketVault: aliceSoldOutTicketAssociatedTokenPublicKey,
+        waitlistTicketVault: bobSoldOutTicketAssociatedTokenPublicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const waitlistAccount = await program.account.waitlist.fetch(
+      waitlistPublicKey
+    );
+    const eventSoldOutTicketAccount = await program.account.eventTicket.fetch(
+      eventSoldOutTicketPublicKey
+    );
+    const afterAliceAccount = await getAccount(
+      provider.connection,
+      aliceAssociatedWalletPublicKey
+    );
+    const bobAccount = await getAccount(
+      provider.connection,
+      bobAssociatedWalletPublicKey
+    );
+    const aliceSoldOutTicketVaultAccount = await getAccount(
+      provider.connection,
+      aliceSoldOutTicketAssociatedTokenPublicKey
+    );
+    const bobSoldOutTicketVaultAccount = await getAccount(
+      provider.connection,
+      bobSoldOutTicketAssociatedTokenPublicKey
+    );
+    assert.isTrue(waitlistAccount.head.eq(new BN(1)));
+    assert.isTrue(waitlistAccount.tail.eq(new BN(1)));
+    assert.equal(eventSoldOutTicketAccount.sold, ticketQuantity);
+    assert.equal(eventSoldOutTicketAccount.waitlisted, 0);
+    assert.equal(
+      afterAliceAccount.amount,
+      beforeAliceAccount.amount + BigInt(ticketPrice)
+    );
+    assert.equal(bobAccount.amount, BigInt(0));
+    assert.equal(aliceSoldOutTicketVaultAccount.amount, BigInt(0));
+    assert.equal(bobSoldOutTicketVaultAccount.amount, BigInt(1));
+  });
+
+  it("should buy assigned seats and check-in one of them", async () => {
+    // arrange
+    const ticketPrice = 30;
+    const ticketQuantity = 10;
+    const seatIds = [0, 7];
+    const eventTheatreTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const seatReservationBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventTheatreTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventTheatreTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventTheatreTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventTheatreTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [seatMapPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("seat_map", "utf-8"), eventTheatreTicketPublicKey.toBuffer()],
+      program.programId
+    );
+    const [seatReservationPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("seat_reservation", "utf-8"),
+          eventTheatreTicketPublicKey.toBuffer(),
+          seatReservationBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const aliceTheatreTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventTheatreTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Theatre",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventTheatreTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .createSeatMap(1, 2, 5)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventTheatreTicketBaseKeypair.publicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceTheatreTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventTheatreTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buySeats(seatIds)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventTheatreTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        feeVault: feeVaultPublicKey,
+        ticketVault: aliceTheatreTicketAssociatedTokenPublicKey,
+        seatReservationBase: seatReservationBaseKeypair.publicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    await program.methods
+      .checkInSeat(seatIds[1])
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        attendee: aliceKeypair.publicKey,
+        collaboratorBase: collaborator1Keypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventTheatreTicketBaseKeypair.publicKey,
+        ticketVault: aliceTheatreTicketAssociatedTokenPublicKey,
+        seatReservationBase: seatReservationBaseKeypair.publicKey,
+      })
+      .signers([aliceKeypair, collaborator1Keypair])
+      .rpc();
+    // assert
+    const seatMapAccount = await program.account.seatMap.fetch(
+      seatMapPublicKey
+    );
+    const seatReservationAccount = await program.account.seatReservation.fetch(
+      seatReservationPublicKey
+    );
+    const eventTheatreTicketAccount = await program.account.eventTicket.fetch(
+      eventTheatreTicketPublicKey
+    );
+    const aliceTheatreTicketVaultAccount = await getAccount(
+      provider.connection,
+      aliceTheatreTicketAssociatedTokenPublicKey
+    );
+    // seat 0 is bit 0 of byte 0 and seat 7 is bit 7 of byte 0
+    assert.equal(seatMapAccount.taken[0], 0b10000001);
+    assert.equal(seatMapAccount.taken[1], 0);
+    assert.isTrue(seatReservationAccount.owner.equals(aliceKeypair.publicKey));
+    assert.isFalse(seatReservationAccount.seats[0].checkedIn);
+    assert.isTrue(seatReservationAccount.seats[1].checkedIn);
+    assert.equal(eventTheatreTicketAccount.sold, seatIds.length);
+    assert.equal(eventTheatreTicketAccount.used, 1);
+    assert.equal(aliceTheatreTicketVaultAccount.amount, BigInt(1));
+  });
+
+  it("should hold 2 general tickets and complete the reservation", async () => {
+    // arrange
+    const generalTicketQuantity = 2;
+    const [ticketHoldPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_hold", "utf-8"),
+          eventGeneralTicketPublicKey.toBuffer(),
+          aliceKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const beforeEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    // act
+    await program.methods
+      .reserveTickets(generalTicketQuantity)
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    const heldEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    await program.methods
+      .completeReservation()
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        feeVault: feeVaultPublicKey,
+        ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const afterEventGeneralTicketAccount =
+      await program.account.eventTicket.fetch(eventGeneralTicketPublicKey);
+    const ticketHoldAccount = await program.account.ticketHold.fetchNullable(
+      ticketHoldPublicKey
+    );
+    assert.equal(
+      heldEventGeneralTicketAccount.held,
+      beforeEventGeneralTicketAccount.held + generalTicketQuantity
+    );
+    assert.equal(
+      afterEventGeneralTicketAccount.held,
+      beforeEventGeneralTicketAccount.held
+    );
+    assert.equal(
+      afterEventGeneralTicketAccount.sold,
+      beforeEventGeneralTicketAccount.sold + generalTicketQuantity
+    );
+    assert.isNull(ticketHoldAccount);
+  });
+
+  it("should check-in a 2-day pass once per day", async () => {
+    // arrange
+    const ticketPrice = 40;
+    const ticketQuantity = 10;
+    const sessions = 2;
+    const eventPassTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventPassTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventPassTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventPassTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventPassTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [passUsagePublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("pass_usage", "utf-8"),
+        eventPassTicketPublicKey.toBuffer(),
+        aliceKeypair.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const alicePassTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventPassTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const checkInPass = (session: number) =>
+      program.methods
+        .checkInPass(session, 1)
+        .accounts({
+          feePayer: provider.wallet.publicKey,
+          attendee: aliceKeypair.publicKey,
+          collaboratorBase: collaborator1Keypair.publicKey,
+          eventBase: eventBaseKeypair.publicKey,
+          eventTicketBase: eventPassTicketBaseKeypair.publicKey,
+          ticketVault: alicePassTicketAssociatedTokenPublicKey,
+        })
+        .signers([aliceKeypair, collaborator1Keypair])
+        .rpc();
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Weekend Pass",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventPassTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .setTicketSessions(sessions)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventPassTicketBaseKeypair.publicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          alicePassTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventPassTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buyTickets(1, null, null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventPassTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: alicePassTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    await checkInPass(0);
+    const firstDayAlicePassTicketVaultAccount = await getAccount(
+      provider.connection,
+      alicePassTicketAssociatedTokenPublicKey
+    );
+    await checkInPass(1);
+    // assert
+    const passUsageAccount = await program.account.passUsage.fetch(
+      passUsagePublicKey
+    );
+    const eventPassTicketAccount = await program.account.eventTicket.fetch(
+      eventPassTicketPublicKey
+    );
+    const lastDayAlicePassTicketVaultAccount = await getAccount(
+      provider.connection,
+      alicePassTicketAssociatedTokenPublicKey
+    );
+    assert.equal(firstDayAlicePassTicketVaultAccount.amount, BigInt(1));
+    assert.isTrue(firstDayAlicePassTicketVaultAccount.isFrozen);
+    assert.equal(lastDayAlicePassTicketVaultAccount.amount, BigInt(0));
+    assert.isFalse(lastDayAlicePassTicketVaultAccount.isFrozen);
+    assert.equal(passUsageAccount.passes, 1);
+    assert.deepEqual(passUsageAccount.sessionsUsed, [1, 1]);
+    assert.equal(eventPassTicketAccount.used, 1);
+  });
+
+  it("should let an attendee leave and re-enter before finalizing", async () => {
+    // arrange
+    const ticketPrice = 10;
+    const ticketQuantity = 10;
+    const eventReentryTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventReentryTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventReentryTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventReentryTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventReentryTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [entryPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("entry", "utf-8"),
+        eventReentryTicketPublicKey.toBuffer(),
+        aliceKeypair.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const aliceReentryTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventReentryTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const entryAccounts = {
+      attendee: aliceKeypair.publicKey,
+      collaboratorBase: collaborator1Keypair.publicKey,
+      eventBase: eventBaseKeypair.publicKey,
+      eventTicketBase: eventReentryTicketBaseKeypair.publicKey,
+    };
+    // act
+    await program.methods
+      .createEventTicket(
+        "Tomorrowland 2022 - Day Ticket",
+        "TMRLND2022",
+        "https://www.gooogle.com",
+        ticketPrice,
+        ticketQuantity
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventReentryTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .postInstructions([
+        await program.methods
+          .setTicketReentry(true)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventReentryTicketBaseKeypair.publicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceReentryTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventReentryTicketMintPublicKey
+        ),
+      ])
+      .rpc();
+    await program.methods
+      .buyTickets(1, null, null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventReentryTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceReentryTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    await program.methods
+      .checkInWithReentry(1)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        ...entryAccounts,
+        ticketVault: aliceReentryTicketAssociatedTokenPublicKey,
+      })
+      .signers([aliceKeypair, collaborator1Keypair])
+      .rpc();
+    const insideAliceReentryTicketVaultAccount = await getAccount(
+      provider.connection,
+      aliceReentryTicketAssociatedTokenPublicKey
+    );
+    await program.methods
+      .checkOut()
+      .accounts(entryAccounts)
+      .signers([collaborator1Keypair])
+      .rpc();
+    const outsideEntryAccount = await program.account.entry.fetch(
+      entryPublicKey
+    );
+    await program.methods
+      .reEnter()
+      .accounts(entryAccounts)
+      .signers([collaborator1Keypair])
+      .rpc();
+    const insideEntryAccount = await program.account.entry.fetch(
+      entryPublicKey
+    );
+    await program.methods
+      .finalizeEntry()
+      .accounts({
+        ...entryAccounts,
+        ticketVault: aliceReentryTicketAssociatedTokenPublicKey,
+      })
+      .signers([aliceKeypair, collaborator1Keypair])
+      .rpc();
+    // assert
+    const eventReentryTicketAccount = await program.account.eventTicket.fetch(
+      eventReentryTicketPublicKey
+    );
+    const aliceReentryTicketVaultAccount = await getAccount(
+      provider.connection,
+      aliceReentryTicketAssociatedTokenPublicKey
+    );
+    const entryAccount = await program.account.entry.fetchNullable(
+      entryPublicKey
+    );
+    assert.isFalse(outsideEntryAccount.inside);
+    assert.isTrue(insideEntryAccount.inside);
+    assert.equal(insideAliceReentryTicketVaultAccount.amount, BigInt(1));
+    assert.isTrue(insideAliceReentryTicketVaultAccount.isFrozen);
+    assert.equal(eventReentryTicketAccount.used, 1);
+    assert.equal(aliceReentryTicketVaultAccount.amount, BigInt(0));
+    assert.isFalse(aliceReentryTicketVaultAccount.isFrozen);
+    assert.isNull(entryAccount);
+  });
+
+  it("should create organizer events with sequential seeds and group them in a series", async () => {
+    // arrange
+    const seriesBaseKeypair = anchor.web3.Keypair.generate();
+    const [organizerPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("organizer", "utf-8"), aliceKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+    const [seriesPublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from("series", "utf-8"),
+        organizerPublicKey.toBuffer(),
+        seriesBaseKeypair.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const organizerEventBasePublicKeys = await Promise.all(
+      [0, 1].map(async (index) => {
+        const indexBuffer = Buffer.alloc(4);
+        indexBuffer.writeUInt32LE(index);
+        const [organizerEventBasePublicKey] =
+          await anchor.web3.PublicKey.findProgramAddress(
+            [
+              Buffer.from("organizer_event", "utf-8"),
+              organizerPublicKey.toBuffer(),
+              indexBuffer,
+            ],
+            program.programId
+          );
+        return organizerEventBasePublicKey;
+      })
+    );
+    const organizerEventPublicKeys = await Promise.all(
+      organizerEventBasePublicKeys.map(async (organizerEventBasePublicKey) => {
+        const [organizerEventPublicKey] =
+          await anchor.web3.PublicKey.findProgramAddress(
+            [
+              Buffer.from("event", "utf-8"),
+              organizerEventBasePublicKey.toBuffer(),
+            ],
+            program.programId
+          );
+        return organizerEventPublicKey;
+      })
+    );
+    // act
+    await program.methods
+      .createOrganizer("Alice Events", "https://alice.events")
+      .accounts({ authority: aliceKeypair.publicKey })
+      .signers([aliceKeypair])
+      .rpc();
+    await program.methods
+      .createSeries("Alice Tour 2022")
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        seriesBase: seriesBaseKeypair.publicKey,
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    for (const [index, eventTitle] of [
+      "Alice Tour Paris",
+      "Alice Tour Berlin",
+    ].entries()) {
+      await program.methods
+        .createOrganizerEvent(eventTitle)
+        .accounts({
+          authority: aliceKeypair.publicKey,
+          organizer: organizerPublicKey,
+          eventBase: organizerEventBasePublicKeys[index],
+          acceptedMint: acceptedMintPublicKey,
+        })
+        .signers([aliceKeypair])
+        .rpc();
+    }
+    await program.methods
+      .addEventToSeries()
+      .accounts({
+        authority: aliceKeypair.publicKey,
+        series: seriesPublicKey,
+        event: organizerEventPublicKeys[1],
+      })
+      .signers([aliceKeypair])
+      .rpc();
+    // assert
+    const organizerAccount = await program.account.organizer.fetch(
+      organizerPublicKey
+    );
+    const seriesAccount = await program.account.series.fetch(seriesPublicKey);
+    const [parisEventAccount, berlinEventAccount] = await Promise.all(
+      organizerEventPublicKeys.map((publicKey) =>
+        program.account.event.fetch(publicKey)
+      )
+    );
+    assert.equal(organizerAccount.eventCount, 2);
+    assert.isFalse(organizerAccount.verified);
+    assert.equal(seriesAccount.eventCount, 1);
+    assert.equal(parisEventAccount.eventTitle, "Alice Tour Paris");
+    assert.isTrue(parisEventAccount.organizer.equals(organizerPublicKey));
+    assert.isNull(parisEventAccount.series);
+    assert.equal(berlinEventAccount.eventTitle, "Alice Tour Berlin");
+    assert.isTrue(berlinEventAccount.series.equals(seriesPublicKey));
+  });
+
+  it("should fail to allocate more tickets than the venue capacity", async () => {
+    // arrange
+    const venueEventBaseKeypair = anchor.web3.Keypair.generate();
+    const venueBaseKeypair = anchor.web3.Keypair.generate();
+    const firstTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const secondTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [venueEventPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event", "utf-8"),
+          venueEventBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [venuePublicKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("venue", "utf-8"), venueBaseKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    let error: AnchorError;
+    // act
+    await program.methods
+      .createEvent("Boom Town")
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: venueEventBaseKeypair.publicKey,
+        acceptedMint: acceptedMintPublicKey,
+      })
+      .rpc();
+    await program.methods
+      .createVenue("Boom Arena", "https://boom.arena", 50)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        venueBase: venueBaseKeypair.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .setEventVenue()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: venueEventBaseKeypair.publicKey,
+        venue: venuePublicKey,
+      })
+      .rpc();
+    await program.methods
+      .createEventTicket(
+        "Boom Town - General",
+        "BOOM",
+        "https://www.gooogle.com",
+        10,
+        40
+      )
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: venueEventBaseKeypair.publicKey,
+        eventTicketBase: firstTicketBaseKeypair.publicKey,
+        metadataProgram: metadataProgramPublicKey,
+      })
+      .rpc();
+    try {
+      await program.methods
+        .createEventTicket(
+          "Boom Town - VIP",
+          "BOOMVIP",
+          "https://www.gooogle.com",
+          20,
+          20
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          eventBase: venueEventBaseKeypair.publicKey,
+          eventTicketBase: secondTicketBaseKeypair.publicKey,
+          metadataProgram: metadataProgramPublicKey,
+        })
+        .rpc();
+    } catch (err) {
+      error = err;
+    }
+    // assert
+    const venueEventAccount = await program.account.event.fetch(
+      venueEventPublicKey
+    );
+    assert.isTrue(venueEventAccount.venue.equals(venuePublicKey));
+    assert.equal(venueEventAccount.totalCapacity, 50);
+    assert.equal(venueEventAccount.totalAllocated, 40);
+    assert.isDefined(error);
+    assert.equal(error.error.errorCode.code, "VenueCapacityExceeded");
+  });
+
+  it("should split the platform fee to the fee treasury", async () => {
+    // arrange
+    const feeBps = 1000;
+    const ticketPrice = 100;
+    const eventFeeTicketBaseKeypair = anchor.web3.Keypair.generate();
+    const [eventFeeTicketPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("event_ticket", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventFeeTicketBaseKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const [eventFeeTicketMintPublicKey] =
+      await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from("ticket_mint", "utf-8"),
+          eventPublicKey.toBuffer(),
+          eventFeeTicketPublicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    const aliceFeeTicketAssociatedTokenPublicKey =
+      await getAssociatedTokenAddress(
+        eventFeeTicketMintPublicKey,
+        aliceKeypair.publicKey
+      );
+    const beforeEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    const beforeFeeVaultAccount = await getAccount(
+      provider.connection,
+      feeVaultPublicKey
+    );
+    // act
+    await program.methods
+      .setPlatformFee(feeBps, feeTreasuryKeypair.publicKey)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+    await program.methods
+      .buyTickets(1, null, null)
+      .accounts({
+        feePayer: provider.wallet.publicKey,
+        authority: aliceKeypair.publicKey,
+        recipient: aliceKeypair.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        eventTicketBase: eventFeeTicketBaseKeypair.publicKey,
+        buyerVault: aliceAssociatedWalletPublicKey,
+        ticketVault: aliceFeeTicketAssociatedTokenPublicKey,
+        feeVault: feeVaultPublicKey,
+      })
+      .preInstructions([
+        await program.methods
+          .createEventTicket(
+            "Tomorrowland 2022 - Fee",
+            "TMRLND2022",
+            "https://www.gooogle.com",
+            ticketPrice,
+            5
+          )
+          .accounts({
+            authority: provider.wallet.publicKey,
+            eventBase: eventBaseKeypair.publicKey,
+            eventTicketBase: eventFeeTicketBaseKeypair.publicKey,
+            metadataProgram: metadataProgramPublicKey,
+          })
+          .instruction(),
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          aliceFeeTicketAssociatedTokenPublicKey,
+          aliceKeypair.publicKey,
+          eventFeeTicketMintPublicKey
+        ),
+      ])
+      .signers([aliceKeypair])
+      .rpc();
+    await program.methods
+      .setPlatformFee(0, feeTreasuryKeypair.publicKey)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+    // assert
+    const afterEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    const afterFeeVaultAccount = await getAccount(
+      provider.connection,
+      feeVaultPublicKey
+    );
+    assert.equal(
+      afterEventVaultAccount.amount,
+      beforeEventVaultAccount.amount + BigInt(90)
+    );
+    assert.equal(
+      afterFeeVaultAccount.amount,
+      beforeFeeVaultAccount.amount + BigInt(10)
+    );
+  });
+
+  it("should reject purchases while the event or the program is paused", async () => {
+    // arrange
+    const buyGeneralTicket = () =>
+      program.methods
+        .buyTickets(1, null, null)
+        .accounts({
+          feePayer: provider.wallet.publicKey,
+          authority: aliceKeypair.publicKey,
+          recipient: aliceKeypair.publicKey,
+          eventBase: eventBaseKeypair.publicKey,
+          eventTicketBase: eventGeneralTicketBaseKeypair.publicKey,
+          buyerVault: aliceAssociatedWalletPublicKey,
+          ticketVault: aliceGeneralTicketAssociatedTokenPublicKey,
+          feeVault: feeVaultPublicKey,
+        })
+        .signers([aliceKeypair])
+        .rpc();
+    let eventPausedError: AnchorError;
+    let programPausedError: AnchorError;
+    // act
+    await program.methods
+      .setEventPaused(true)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+      })
+      .rpc();
+    try {
+      await buyGeneralTicket();
+    } catch (err) {
+      eventPausedError = err;
+    }
+    await program.methods
+      .setEventPaused(false)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .setPaused(true)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+    try {
+      await buyGeneralTicket();
+    } catch (err) {
+      programPausedError = err;
+    }
+    await program.methods
+      .setPaused(false)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+    // assert
+    const eventAccount = await program.account.event.fetch(eventPublicKey);
+    assert.isFalse(eventAccount.paused);
+    assert.isDefined(eventPausedError);
+    assert.equal(eventPausedError.error.errorCode.code, "EventPaused");
+    assert.isDefined(programPausedError);
+    assert.equal(programPausedError.error.errorCode.code, "ProgramPaused");
+  });
+
+  it("should withdraw funds from the event vault", async () => {
+    // arrange
+    const amount = 10;
+    const destinationVaultPublicKey = await createUserAndAssociatedWallet(
+      provider,
+      acceptedMintPublicKey,
+      0,
+      anchor.web3.Keypair.generate()
+    );
+    const beforeEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    // act
+    await program.methods
+      .withdraw(new BN(amount))
+      .accounts({
+        authority: provider.wallet.publicKey,
+        eventBase: eventBaseKeypair.publicKey,
+        destinationVault: destinationVaultPublicKey,
+      })
+      .rpc();
+    // assert
+    const afterEventVaultAccount = await getAccount(
+      provider.connection,
+      eventVaultPublicKey
+    );
+    const destinationVaultAccount = await getAccount(
+      provider.connection,
+      destinationVaultPublicKey
+    );
+    assert.equal(
+      afterEventVaultAccount.amount,
+      beforeEventVaultAccount.amount - BigInt(amount)
+    );
+    assert.equal(destinationVaultAccount.amount, BigInt(amount));
+  });
+
   it("should fail when there are not enough tickets available", async () => {
     // arrange
     let error: AnchorError;
@@ -725,13 +2434,16 @@ describe("disco", () => {
     // act
     try {
       await program.methods
-        .buyTickets(ticketToBuy)
+        .buyTickets(ticketToBuy, null, null)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           authority: aliceKeypair.publicKey,
+          recipient: aliceKeypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,
           eventTicketBase: eventUltraVipTicketBaseKeypair.publicKey,
           buyerVault: aliceAssociatedWalletPublicKey,
           ticketVault: aliceUltraVipTicketAssociatedTokenPublicKey,
+          feeVault: feeVaultPublicKey,
         })
         .preInstructions([
           await program.methods
@@ -774,6 +2486,7 @@ describe("disco", () => {
       await program.methods
         .checkIn(1)
         .accounts({
+          feePayer: provider.wallet.publicKey,
           attendee: aliceKeypair.publicKey,
           collaboratorBase: collaborator1Keypair.publicKey,
           eventBase: eventBaseKeypair.publicKey,